@@ -0,0 +1,303 @@
+use std::time::Duration;
+
+use crate::Colour;
+
+/// Values that can be blended between two keyframes.
+pub trait Lerp
+{
+	fn lerp(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32
+{
+	fn lerp(&self, other: &f32, t: f32) -> f32
+	{
+		self + (other - self) * t
+	}
+}
+
+impl Lerp for f64
+{
+	fn lerp(&self, other: &f64, t: f32) -> f64
+	{
+		self + (other - self) * t as f64
+	}
+}
+
+impl Lerp for i32
+{
+	fn lerp(&self, other: &i32, t: f32) -> i32
+	{
+		(*self as f32).lerp(&(*other as f32), t).round() as i32
+	}
+}
+
+impl Lerp for u8
+{
+	fn lerp(&self, other: &u8, t: f32) -> u8
+	{
+		(*self as f32).lerp(&(*other as f32), t).round().clamp(0.0, 255.0) as u8
+	}
+}
+
+impl Lerp for Colour
+{
+	fn lerp(&self, other: &Colour, t: f32) -> Colour
+	{
+		Colour::from_rgba(
+			self.r.lerp(&other.r, t),
+			self.g.lerp(&other.g, t),
+			self.b.lerp(&other.b, t),
+			self.a.lerp(&other.a, t),
+		)
+	}
+}
+
+impl<A: Lerp, B: Lerp> Lerp for (A, B)
+{
+	fn lerp(&self, other: &(A, B), t: f32) -> (A, B)
+	{
+		(self.0.lerp(&other.0, t), self.1.lerp(&other.1, t))
+	}
+}
+
+/// How a keyframe blends into the next one.
+#[derive(Clone, Copy)]
+pub enum Interpolation
+{
+	/// Holds the value until the next keyframe.
+	Step,
+	Linear,
+	EaseIn,
+	EaseOut,
+	EaseInOut,
+}
+
+impl Interpolation
+{
+	pub fn apply(&self, t: f32) -> f32
+	{
+		match self
+		{
+			Interpolation::Step => 0.0,
+			Interpolation::Linear => t,
+			Interpolation::EaseIn => t * t,
+			Interpolation::EaseOut => t * (2.0 - t),
+			Interpolation::EaseInOut => t * t * (3.0 - 2.0 * t),
+		}
+	}
+}
+
+pub struct Keyframe<T>
+{
+	/// Time in seconds from the start of the track.
+	pub time: f32,
+	pub value: T,
+	pub interpolation: Interpolation,
+}
+
+pub struct Track<T>
+{
+	keyframes: Vec<Keyframe<T>>,
+}
+
+impl<T: Lerp + Clone> Track<T>
+{
+	pub fn new() -> Track<T>
+	{
+		Track {
+			keyframes: Vec::new(),
+		}
+	}
+
+	/// Keyframes may be added in any order.
+	pub fn add_keyframe(&mut self, time: f32, value: T, interpolation: Interpolation)
+	{
+		let index = self.keyframes.partition_point(|keyframe| keyframe.time <= time);
+		self.keyframes.insert(index, Keyframe {
+			time: time,
+			value: value,
+			interpolation: interpolation,
+		});
+	}
+
+	pub fn keyframes(&self) -> &[Keyframe<T>]
+	{
+		&self.keyframes
+	}
+
+	/// The time of the last keyframe.
+	pub fn duration(&self) -> f32
+	{
+		match self.keyframes.last()
+		{
+			Some(keyframe) => keyframe.time,
+			None => 0.0,
+		}
+	}
+
+	/// Returns the value at `time`, holding the first and last values outside the track. `None` if the track is empty.
+	pub fn sample(&self, time: f32) -> Option<T>
+	{
+		let next_index = self.keyframes.partition_point(|keyframe| keyframe.time <= time);
+		if next_index == 0
+		{
+			return self.keyframes.first().map(|keyframe| keyframe.value.clone());
+		}
+		let previous = &self.keyframes[next_index - 1];
+		match self.keyframes.get(next_index)
+		{
+			Some(next) => {
+				let t = (time - previous.time) / (next.time - previous.time);
+				Some(previous.value.lerp(&next.value, previous.interpolation.apply(t)))
+			},
+			None => Some(previous.value.clone()),
+		}
+	}
+}
+
+impl<T: Lerp + Clone> Default for Track<T>
+{
+	fn default() -> Track<T>
+	{
+		Track::new()
+	}
+}
+
+/// Plays any number of tracks together, passing each sampled value to a closure that applies it
+/// (for example to a camera position, a sprite tint or a rect's colour).
+pub struct AnimationPlayer<'a>
+{
+	pub time: f32,
+	pub speed: f32,
+	pub looping: bool,
+	pub playing: bool,
+	duration: f32,
+	bindings: Vec<Box<dyn FnMut(f32) + 'a>>,
+}
+
+impl<'a> AnimationPlayer<'a>
+{
+	pub fn new() -> AnimationPlayer<'a>
+	{
+		AnimationPlayer {
+			time: 0.0,
+			speed: 1.0,
+			looping: false,
+			playing: true,
+			duration: 0.0,
+			bindings: Vec::new(),
+		}
+	}
+
+	pub fn add_track<T: Lerp + Clone + 'a>(&mut self, track: Track<T>, mut apply: impl FnMut(T) + 'a)
+	{
+		self.duration = self.duration.max(track.duration());
+		self.bindings.push(Box::new(move |time| {
+			if let Some(value) = track.sample(time)
+			{
+				apply(value);
+			}
+		}));
+	}
+
+	/// The duration of the longest track.
+	pub fn duration(&self) -> f32
+	{
+		self.duration
+	}
+
+	pub fn is_finished(&self) -> bool
+	{
+		!self.looping && self.time >= self.duration
+	}
+
+	pub fn seek(&mut self, time: f32)
+	{
+		self.time = time;
+		self.apply();
+	}
+
+	pub fn update(&mut self, delta: Duration)
+	{
+		if !self.playing
+			{ return }
+
+		self.time += delta.as_secs_f32() * self.speed;
+		if self.looping && self.duration > 0.0
+		{
+			self.time = self.time.rem_euclid(self.duration);
+		}
+		else
+		{
+			self.time = self.time.clamp(0.0, self.duration);
+		}
+		self.apply();
+	}
+
+	fn apply(&mut self)
+	{
+		for binding in &mut self.bindings
+		{
+			binding(self.time);
+		}
+	}
+}
+
+impl<'a> Default for AnimationPlayer<'a>
+{
+	fn default() -> AnimationPlayer<'a>
+	{
+		AnimationPlayer::new()
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn track() -> Track<f32>
+	{
+		let mut track = Track::new();
+		track.add_keyframe(2.0, 20.0, Interpolation::Step);
+		track.add_keyframe(1.0, 10.0, Interpolation::Linear);
+		track.add_keyframe(3.0, 40.0, Interpolation::Linear);
+		track
+	}
+
+	#[test]
+	fn sample_holds_the_first_value_before_the_track()
+	{
+		assert_eq!(track().sample(0.0), Some(10.0));
+		assert_eq!(track().sample(-5.0), Some(10.0));
+	}
+
+	#[test]
+	fn sample_holds_the_last_value_after_the_track()
+	{
+		assert_eq!(track().sample(3.5), Some(40.0));
+		assert_eq!(track().sample(100.0), Some(40.0));
+	}
+
+	#[test]
+	fn sample_on_a_keyframe_returns_its_value()
+	{
+		assert_eq!(track().sample(1.0), Some(10.0));
+		assert_eq!(track().sample(2.0), Some(20.0));
+		assert_eq!(track().sample(3.0), Some(40.0));
+	}
+
+	#[test]
+	fn sample_between_keyframes_uses_the_earlier_interpolation()
+	{
+		assert_eq!(track().sample(1.5), Some(15.0));
+		assert_eq!(track().sample(2.5), Some(20.0));
+	}
+
+	#[test]
+	fn sample_of_an_empty_track_is_none()
+	{
+		assert_eq!(Track::<f32>::new().sample(1.0), None);
+	}
+}
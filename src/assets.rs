@@ -0,0 +1,62 @@
+use sdl2::render::TextureCreator as SdlTextureCreator;
+use sdl2::video::WindowContext as SdlWindowContext;
+
+use crate::sprite::Texture;
+use crate::{Colour, RealmsError};
+
+const MISSING_TEXTURE_SIZE: u32 = 16;
+const MISSING_TEXTURE_CHECKER_SIZE: u32 = 8;
+
+/// Textures that are always available, so there is something sensible to draw when loading an asset fails.
+pub struct Builtin<'a>
+{
+	/// A 1x1 white texture.
+	pub white: Texture<'a>,
+	/// A magenta and black checkerboard, used in place of textures that could not be loaded.
+	pub missing: Texture<'a>,
+}
+
+pub fn builtin<'a>(texture_creator: &'a SdlTextureCreator<SdlWindowContext>) -> Result<Builtin<'a>, RealmsError>
+{
+	Ok(Builtin {
+		white: white_texture(texture_creator)?,
+		missing: missing_texture(texture_creator)?,
+	})
+}
+
+pub fn white_texture<'a>(texture_creator: &'a SdlTextureCreator<SdlWindowContext>) -> Result<Texture<'a>, RealmsError>
+{
+	Texture::from_pixels(&[Colour::from_rgb(255, 255, 255)], 1, 1, texture_creator)
+}
+
+pub fn missing_texture<'a>(texture_creator: &'a SdlTextureCreator<SdlWindowContext>) -> Result<Texture<'a>, RealmsError>
+{
+	let mut pixels: Vec<Colour> = Vec::new();
+	for y in 0..MISSING_TEXTURE_SIZE
+	{
+		for x in 0..MISSING_TEXTURE_SIZE
+		{
+			let checker = (x / MISSING_TEXTURE_CHECKER_SIZE + y / MISSING_TEXTURE_CHECKER_SIZE).is_multiple_of(2);
+			pixels.push(match checker
+			{
+				true => Colour::from_rgb(255, 0, 255),
+				false => Colour::from_rgb(0, 0, 0),
+			});
+		}
+	}
+	Texture::from_pixels(&pixels, MISSING_TEXTURE_SIZE, MISSING_TEXTURE_SIZE, texture_creator)
+}
+
+/// Loads the texture at `filepath`, or the missing texture if it cannot be loaded.
+/// The reason loading failed is printed to stderr, so the problem is not hidden.
+pub fn load_texture_or_missing<'a>(filepath: &str, texture_creator: &'a SdlTextureCreator<SdlWindowContext>) -> Result<Texture<'a>, RealmsError>
+{
+	match Texture::new(filepath, texture_creator)
+	{
+		Ok(texture) => Ok(texture),
+		Err(error) => {
+			eprintln!("Realms warning: could not load texture {:?}, using the missing texture instead: {}", filepath, error.get_error_info());
+			missing_texture(texture_creator)
+		},
+	}
+}
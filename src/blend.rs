@@ -0,0 +1,51 @@
+use sdl2::render::Canvas;
+use sdl2::sys::{self, SDL_BlendMode};
+use sdl2::video::Window as SdlWindow;
+
+use crate::sprite::Texture;
+use crate::RealmsError;
+
+/// Controls how a shape or sprite is combined with what has already been drawn.
+/// Blending uses the alpha value of the `Colour`, so a fully opaque `Alpha` draw looks the same as `None`.
+#[derive(Clone, PartialEq)]
+pub enum BlendMode
+{
+	None,
+	Alpha,
+	Additive,
+	Modulate,
+	Multiply,
+}
+
+impl BlendMode
+{
+	pub fn to_sdl(&self) -> SDL_BlendMode
+	{
+		match self
+		{
+			BlendMode::None => SDL_BlendMode::SDL_BLENDMODE_NONE,
+			BlendMode::Alpha => SDL_BlendMode::SDL_BLENDMODE_BLEND,
+			BlendMode::Additive => SDL_BlendMode::SDL_BLENDMODE_ADD,
+			BlendMode::Modulate => SDL_BlendMode::SDL_BLENDMODE_MOD,
+			BlendMode::Multiply => SDL_BlendMode::SDL_BLENDMODE_MUL,
+		}
+	}
+}
+
+pub(crate) fn set_draw_blend_mode(canvas: &Canvas<SdlWindow>, blend_mode: &BlendMode) -> Result<(), RealmsError>
+{
+	match unsafe { sys::SDL_SetRenderDrawBlendMode(canvas.raw(), blend_mode.to_sdl()) }
+	{
+		0 => Ok(()),
+		_ => Err(RealmsError::SetBlendModeError(sdl2::get_error())),
+	}
+}
+
+pub(crate) fn set_texture_blend_mode(texture: &Texture, blend_mode: &BlendMode) -> Result<(), RealmsError>
+{
+	match unsafe { sys::SDL_SetTextureBlendMode(texture.sdl_texture.raw(), blend_mode.to_sdl()) }
+	{
+		0 => Ok(()),
+		_ => Err(RealmsError::SetBlendModeError(sdl2::get_error())),
+	}
+}
@@ -1,11 +1,12 @@
 use sdl2::pixels::Color as SdlColor;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct Colour
 {
 	pub r: u8,
 	pub g: u8,
 	pub b: u8,
+	pub a: u8,
 }
 
 impl Colour
@@ -16,6 +17,7 @@ impl Colour
 			r: 0,
 			g: 0,
 			b: 0,
+			a: 255,
 		}
 	}
 
@@ -25,11 +27,22 @@ impl Colour
 			r: red,
 			g: green,
 			b: blue,
+			a: 255,
+		}
+	}
+
+	pub fn from_rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Colour
+	{
+		Colour {
+			r: red,
+			g: green,
+			b: blue,
+			a: alpha,
 		}
 	}
 
 	pub fn to_sdl(&self) -> SdlColor
 	{
-		SdlColor::RGB(self.r, self.g, self.b)
+		SdlColor::RGBA(self.r, self.g, self.b, self.a)
 	}
 }
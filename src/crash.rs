@@ -0,0 +1,40 @@
+use std::backtrace::Backtrace;
+use std::env;
+use std::fs;
+use std::panic;
+use std::path::PathBuf;
+
+use sdl2::messagebox::{show_simple_message_box, MessageBoxFlag};
+use sdl2::video::Window as SdlWindow;
+
+/// Replaces the panic hook so that a panic writes a crash log next to the executable
+/// and shows a native error dialog, instead of the window just disappearing.
+/// The previous hook still runs first, so the panic is also printed to stderr as usual.
+pub fn install_panic_hook(game_name: &str)
+{
+	let game_name = game_name.to_string();
+	let previous_hook = panic::take_hook();
+	panic::set_hook(Box::new(move |panic_info| {
+		previous_hook(panic_info);
+
+		let backtrace = Backtrace::force_capture();
+		let report = format!("{}\n\nBacktrace:\n{}", panic_info, backtrace);
+		let mut message = format!("{} crashed unexpectedly.\n\n{}", game_name, panic_info);
+		if let Some(log_path) = crash_log_path()
+		{
+			if fs::write(&log_path, report).is_ok()
+			{
+				message.push_str(&format!("\n\nA crash log was saved to {}", log_path.display()));
+			}
+		}
+
+		let _ = show_simple_message_box(MessageBoxFlag::ERROR, &game_name, &message, None::<&SdlWindow>);
+	}));
+}
+
+fn crash_log_path() -> Option<PathBuf>
+{
+	let executable = env::current_exe().ok()?;
+	let file_name = format!("{}-crash.log", executable.file_stem()?.to_string_lossy());
+	Some(executable.with_file_name(file_name))
+}
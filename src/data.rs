@@ -0,0 +1,162 @@
+use sdl2::rect::Rect as SdlRect;
+
+use crate::math::Vec2;
+
+/// An axis-aligned rectangle, with (`x`, `y`) at its top-left corner.
+/// Unlike `shape::Rect`, it is not drawable: it is for bounds, hit testing and overlap checks.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Rect
+{
+	pub x: f32,
+	pub y: f32,
+	pub w: f32,
+	pub h: f32,
+}
+
+impl Rect
+{
+	pub fn new(x: f32, y: f32, w: f32, h: f32) -> Rect
+	{
+		Rect {
+			x: x,
+			y: y,
+			w: w,
+			h: h,
+		}
+	}
+
+	/// Creates a rect from its top-left (`min`) and bottom-right (`max`) corners.
+	pub fn from_min_max(min: Vec2, max: Vec2) -> Rect
+	{
+		Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+	}
+
+	pub fn from_center(center: Vec2, w: f32, h: f32) -> Rect
+	{
+		Rect::new(center.x - w / 2.0, center.y - h / 2.0, w, h)
+	}
+
+	pub fn min(&self) -> Vec2
+	{
+		Vec2::new(self.x, self.y)
+	}
+
+	pub fn max(&self) -> Vec2
+	{
+		Vec2::new(self.x + self.w, self.y + self.h)
+	}
+
+	pub fn center(&self) -> Vec2
+	{
+		Vec2::new(self.x + self.w / 2.0, self.y + self.h / 2.0)
+	}
+
+	pub fn area(&self) -> f32
+	{
+		self.w * self.h
+	}
+
+	/// Points on the top and left edges are inside; points on the bottom and right edges are not,
+	/// so rects that share an edge never both contain the same point.
+	pub fn contains(&self, point: Vec2) -> bool
+	{
+		point.x >= self.x && point.x < self.x + self.w
+			&& point.y >= self.y && point.y < self.y + self.h
+	}
+
+	pub fn contains_rect(&self, other: &Rect) -> bool
+	{
+		other.x >= self.x && other.x + other.w <= self.x + self.w
+			&& other.y >= self.y && other.y + other.h <= self.y + self.h
+	}
+
+	/// Rects that only touch along an edge do not intersect.
+	pub fn intersects(&self, other: &Rect) -> bool
+	{
+		self.x < other.x + other.w && other.x < self.x + self.w
+			&& self.y < other.y + other.h && other.y < self.y + self.h
+	}
+
+	/// The overlapping area of the two rects, or `None` if they do not intersect.
+	pub fn intersection(&self, other: &Rect) -> Option<Rect>
+	{
+		if !self.intersects(other)
+			{ return None }
+		let min = Vec2::new(self.x.max(other.x), self.y.max(other.y));
+		let max = Vec2::new((self.x + self.w).min(other.x + other.w), (self.y + self.h).min(other.y + other.h));
+		Some(Rect::from_min_max(min, max))
+	}
+
+	/// The smallest rect containing both rects.
+	pub fn union(&self, other: &Rect) -> Rect
+	{
+		let min = Vec2::new(self.x.min(other.x), self.y.min(other.y));
+		let max = Vec2::new((self.x + self.w).max(other.x + other.w), (self.y + self.h).max(other.y + other.h));
+		Rect::from_min_max(min, max)
+	}
+
+	/// Grows the rect by `amount` on every side, keeping its centre. A negative `amount` shrinks it.
+	pub fn inflate(&self, amount: f32) -> Rect
+	{
+		Rect::new(self.x - amount, self.y - amount, self.w + amount * 2.0, self.h + amount * 2.0)
+	}
+
+	pub fn translate(&self, offset: Vec2) -> Rect
+	{
+		Rect::new(self.x + offset.x, self.y + offset.y, self.w, self.h)
+	}
+
+	/// Rounds to whole pixels. Negative sizes become zero.
+	pub fn to_sdl(&self) -> SdlRect
+	{
+		SdlRect::new(self.x.round() as i32, self.y.round() as i32, self.w.round().max(0.0) as u32, self.h.round().max(0.0) as u32)
+	}
+
+	pub fn from_sdl(sdl_rect: &SdlRect) -> Rect
+	{
+		Rect::new(sdl_rect.x() as f32, sdl_rect.y() as f32, sdl_rect.width() as f32, sdl_rect.height() as f32)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	#[test]
+	fn intersection_of_overlapping_rects()
+	{
+		let a = Rect::new(0.0, 0.0, 10.0, 10.0);
+		let b = Rect::new(5.0, -5.0, 10.0, 10.0);
+		assert_eq!(a.intersection(&b), Some(Rect::new(5.0, 0.0, 5.0, 5.0)));
+		assert_eq!(b.intersection(&a), a.intersection(&b));
+	}
+
+	#[test]
+	fn intersection_of_a_contained_rect_is_that_rect()
+	{
+		let outer = Rect::new(0.0, 0.0, 10.0, 10.0);
+		let inner = Rect::new(2.0, 3.0, 4.0, 5.0);
+		assert_eq!(outer.intersection(&inner), Some(inner));
+	}
+
+	#[test]
+	fn disjoint_rects_do_not_intersect()
+	{
+		let a = Rect::new(0.0, 0.0, 10.0, 10.0);
+		let b = Rect::new(20.0, 20.0, 5.0, 5.0);
+		assert!(!a.intersects(&b));
+		assert_eq!(a.intersection(&b), None);
+	}
+
+	#[test]
+	fn touching_rects_do_not_intersect()
+	{
+		let a = Rect::new(0.0, 0.0, 10.0, 10.0);
+		for b in [Rect::new(10.0, 0.0, 5.0, 5.0), Rect::new(0.0, 10.0, 5.0, 5.0), Rect::new(10.0, 10.0, 5.0, 5.0)]
+		{
+			assert!(!a.intersects(&b));
+			assert_eq!(a.intersection(&b), None);
+		}
+	}
+}
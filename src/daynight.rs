@@ -0,0 +1,212 @@
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use crate::anim::{Interpolation, Track};
+use crate::math::Angle;
+use crate::Colour;
+
+// Times of day as a fraction of the whole day, where 0.0 is midnight and 0.5 is noon.
+const DAWN_START: f32 = 0.2;
+const DAY_START: f32 = 0.3;
+const DUSK_START: f32 = 0.7;
+const NIGHT_START: f32 = 0.8;
+const TIMES_OF_DAY: usize = 4;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TimeOfDay
+{
+	Dawn,
+	Day,
+	Dusk,
+	Night,
+}
+
+impl TimeOfDay
+{
+	fn at(time: f32) -> TimeOfDay
+	{
+		match time
+		{
+			time if time < DAWN_START => TimeOfDay::Night,
+			time if time < DAY_START => TimeOfDay::Dawn,
+			time if time < DUSK_START => TimeOfDay::Day,
+			time if time < NIGHT_START => TimeOfDay::Dusk,
+			_ => TimeOfDay::Night,
+		}
+	}
+}
+
+/// Advances a clock that loops once every `day_length`, and works out how the world should look at that time:
+/// an ambient tint to draw over the scene, where the sun is, and how visible the stars are.
+///
+/// Times of day are given as a fraction of the whole day: `0.0` is midnight, `0.25` is sunrise, `0.5` is noon
+/// and `0.75` is sunset. Dawn lasts from `0.2` to `0.3` and dusk from `0.7` to `0.8`.
+pub struct DayNightCycle
+{
+	pub day_length: Duration,
+	time: f32,
+	tint: Track<Colour>,
+}
+
+impl DayNightCycle
+{
+	/// Starts at noon, with a tint that goes from dark blue at night to orange at dawn and dusk, and to white
+	/// (no tint) during the day.
+	pub fn new(day_length: Duration) -> DayNightCycle
+	{
+		let mut tint = Track::new();
+		tint.add_keyframe(0.0, Colour::from_rgb(40, 50, 110), Interpolation::Linear);
+		tint.add_keyframe(DAWN_START, Colour::from_rgb(40, 50, 110), Interpolation::Linear);
+		tint.add_keyframe(0.25, Colour::from_rgb(255, 170, 130), Interpolation::Linear);
+		tint.add_keyframe(DAY_START, Colour::from_rgb(255, 255, 255), Interpolation::Linear);
+		tint.add_keyframe(DUSK_START, Colour::from_rgb(255, 255, 255), Interpolation::Linear);
+		tint.add_keyframe(0.75, Colour::from_rgb(255, 140, 100), Interpolation::Linear);
+		tint.add_keyframe(NIGHT_START, Colour::from_rgb(40, 50, 110), Interpolation::Linear);
+		tint.add_keyframe(1.0, Colour::from_rgb(40, 50, 110), Interpolation::Linear);
+
+		DayNightCycle {
+			day_length: day_length,
+			time: 0.5,
+			tint: tint,
+		}
+	}
+
+	/// Replaces the ambient tint. Keyframe times are fractions of the day, from `0.0` to `1.0`.
+	pub fn set_tint(&mut self, tint: Track<Colour>)
+	{
+		self.tint = tint;
+	}
+
+	/// Moves the clock forward by `delta`, returning each time of day that began, in order.
+	/// If `delta` is longer than a day, each time of day is returned at most once.
+	/// Pass `delta` through a `TimeScale` first to pause or speed up the cycle.
+	pub fn update(&mut self, delta: Duration) -> Vec<TimeOfDay>
+	{
+		let day_length = self.day_length.as_secs_f32();
+		if day_length <= 0.0
+			{ return Vec::new() }
+
+		let mut started: Vec<TimeOfDay> = Vec::new();
+		let mut remaining = delta.as_secs_f32() / day_length;
+		// Whole days are skipped, so a huge `delta` (for example after the game was suspended) costs at most one lap.
+		if remaining > 1.0
+		{
+			remaining = 1.0 + remaining % 1.0;
+		}
+		while remaining > 0.0
+		{
+			let next_boundary = [DAWN_START, DAY_START, DUSK_START, NIGHT_START, 1.0].into_iter()
+				.find(|boundary| *boundary > self.time)
+				.unwrap_or(1.0);
+			if self.time + remaining < next_boundary
+			{
+				self.time += remaining;
+				break;
+			}
+			remaining -= next_boundary - self.time;
+			self.time = next_boundary % 1.0;
+			let time_of_day = TimeOfDay::at(self.time);
+			// Midnight wraps the clock but is not the start of a new time of day.
+			if next_boundary < 1.0
+			{
+				started.push(time_of_day);
+			}
+		}
+		let repeated = started.len().saturating_sub(TIMES_OF_DAY);
+		started.drain(..repeated);
+		started
+	}
+
+	/// The current time as a fraction of the day, from `0.0` (midnight) up to `1.0`.
+	pub fn get_time(&self) -> f32
+	{
+		self.time
+	}
+
+	pub fn set_time(&mut self, time: f32)
+	{
+		self.time = time.rem_euclid(1.0);
+	}
+
+	/// The current time on a 24-hour clock, from `0.0` up to `24.0`.
+	pub fn get_hour(&self) -> f32
+	{
+		self.time * 24.0
+	}
+
+	pub fn time_of_day(&self) -> TimeOfDay
+	{
+		TimeOfDay::at(self.time)
+	}
+
+	/// The colour to multiply the scene by, for example by drawing a full-window `Rect` with `BlendMode::Multiply`.
+	pub fn ambient_tint(&self) -> Colour
+	{
+		self.tint.sample(self.time).unwrap_or(Colour::from_rgb(255, 255, 255))
+	}
+
+	/// How bright daylight is, from `0.0` at night to `1.0` during the day, fading during dawn and dusk.
+	pub fn daylight(&self) -> f32
+	{
+		match self.time_of_day()
+		{
+			TimeOfDay::Night => 0.0,
+			TimeOfDay::Dawn => (self.time - DAWN_START) / (DAY_START - DAWN_START),
+			TimeOfDay::Day => 1.0,
+			TimeOfDay::Dusk => 1.0 - (self.time - DUSK_START) / (NIGHT_START - DUSK_START),
+		}
+	}
+
+	/// How opaque star sprites should be, from `0` during the day to `255` at night.
+	pub fn star_alpha(&self) -> u8
+	{
+		((1.0 - self.daylight()) * 255.0).round() as u8
+	}
+
+	/// The direction of the sun from the centre of its orbit. It rises on the right (`Angle::ZERO`) at sunrise,
+	/// is straight up at noon and sets on the left at sunset. Use `Vec2::from_angle()` to place a sun sprite.
+	pub fn sun_angle(&self) -> Angle
+	{
+		Angle::from_radians(-(self.time - 0.25) * TAU).wrapped()
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn cycle_at(time: f32) -> DayNightCycle
+	{
+		let mut cycle = DayNightCycle::new(Duration::from_secs(100));
+		cycle.set_time(time);
+		cycle
+	}
+
+	#[test]
+	fn update_wraps_past_midnight()
+	{
+		let mut cycle = cycle_at(0.75);
+		let started = cycle.update(Duration::from_secs(50));
+		assert_eq!(started, vec![TimeOfDay::Night, TimeOfDay::Dawn]);
+		assert!((cycle.get_time() - 0.25).abs() < 0.001);
+	}
+
+	#[test]
+	fn update_longer_than_a_day_reports_each_time_of_day_once()
+	{
+		let mut cycle = cycle_at(0.5);
+		let started = cycle.update(Duration::from_secs(1010));
+		assert_eq!(started, vec![TimeOfDay::Dusk, TimeOfDay::Night, TimeOfDay::Dawn, TimeOfDay::Day]);
+		assert!((cycle.get_time() - 0.6).abs() < 0.001);
+	}
+
+	#[test]
+	fn update_skips_whole_days_of_a_huge_delta()
+	{
+		let mut cycle = cycle_at(0.5);
+		let started = cycle.update(Duration::from_secs(100 * 1_000_000));
+		assert_eq!(started.len(), TIMES_OF_DAY);
+		assert!((cycle.get_time() - 0.5).abs() < 0.001);
+	}
+}
@@ -0,0 +1,132 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sdl2::image::{LoadSurface, SaveSurface};
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::surface::Surface;
+
+use crate::window::Window;
+use crate::RealmsError;
+
+const CAPTURE_DIRECTORY: &str = "realms-captures";
+const BYTES_PER_PIXEL: usize = 3;
+
+pub struct CaptureDiff
+{
+	pub total_pixels: usize,
+	/// Pixels where any channel differs from the previous capture.
+	pub differing_pixels: usize,
+	/// The largest difference in any single channel, from `0` to `255`.
+	pub max_difference: u8,
+	/// The average channel difference across the whole frame.
+	pub mean_difference: f32,
+	/// `None` if the two captures had different sizes, so no per-pixel diff could be made.
+	pub diff_image: Option<PathBuf>,
+}
+
+/// Saves what has been drawn this frame to `realms-captures/<name>.png`. If a capture with the same name
+/// already exists, it is compared with the new frame first: a diff image is saved to `realms-captures/<name>-diff.png`
+/// (brighter pixels differ more) and the summary is returned.
+///
+/// Call this after drawing but **before** `window.draw()`, since the frame is no longer readable once presented.
+pub fn capture_and_compare(window: &Window, name: &str) -> Result<Option<CaptureDiff>, RealmsError>
+{
+	let (width, height) = match window.sdl.canvas.output_size()
+	{
+		Ok(size) => size,
+		Err(error_info) => return Err(RealmsError::CaptureFrameError(error_info)),
+	};
+	let mut pixels = match window.sdl.canvas.read_pixels(None, PixelFormatEnum::RGB24)
+	{
+		Ok(pixels) => pixels,
+		Err(error_info) => return Err(RealmsError::CaptureFrameError(error_info)),
+	};
+
+	if let Err(error_info) = fs::create_dir_all(CAPTURE_DIRECTORY)
+	{
+		return Err(RealmsError::CaptureFrameError(error_info.to_string()));
+	}
+	let capture_path = Path::new(CAPTURE_DIRECTORY).join(format!("{}.png", name));
+	let diff_path = Path::new(CAPTURE_DIRECTORY).join(format!("{}-diff.png", name));
+
+	let diff = match capture_path.exists()
+	{
+		true => Some(compare_with_previous(&capture_path, &diff_path, &pixels, width, height)?),
+		false => None,
+	};
+
+	save_pixels(&mut pixels, width, height, &capture_path)?;
+	Ok(diff)
+}
+
+fn compare_with_previous(capture_path: &Path, diff_path: &Path, pixels: &[u8], width: u32, height: u32) -> Result<CaptureDiff, RealmsError>
+{
+	let previous = match Surface::from_file(capture_path).and_then(|surface| surface.convert_format(PixelFormatEnum::RGB24))
+	{
+		Ok(previous) => previous,
+		Err(error_info) => return Err(RealmsError::CaptureFrameError(error_info)),
+	};
+
+	let total_pixels = (width * height) as usize;
+	if previous.width() != width || previous.height() != height
+	{
+		return Ok(CaptureDiff {
+			total_pixels: total_pixels,
+			differing_pixels: total_pixels,
+			max_difference: 255,
+			mean_difference: 255.0,
+			diff_image: None,
+		});
+	}
+
+	let row_length = width as usize * BYTES_PER_PIXEL;
+	let previous_pitch = previous.pitch() as usize;
+	let mut diff_pixels: Vec<u8> = vec![0; pixels.len()];
+	let mut differing_pixels: usize = 0;
+	let mut max_difference: u8 = 0;
+	let mut total_difference: u64 = 0;
+
+	previous.with_lock(|previous_pixels| {
+		for y in 0..height as usize
+		{
+			let previous_row = &previous_pixels[y * previous_pitch..y * previous_pitch + row_length];
+			let current_row = &pixels[y * row_length..(y + 1) * row_length];
+			for x in 0..width as usize
+			{
+				let mut pixel_differs = false;
+				for channel in 0..BYTES_PER_PIXEL
+				{
+					let index = x * BYTES_PER_PIXEL + channel;
+					let difference = previous_row[index].abs_diff(current_row[index]);
+					pixel_differs |= difference > 0;
+					max_difference = max_difference.max(difference);
+					total_difference += difference as u64;
+					diff_pixels[y * row_length + index] = difference.saturating_mul(4);
+				}
+				if pixel_differs
+				{
+					differing_pixels += 1;
+				}
+			}
+		}
+	});
+
+	save_pixels(&mut diff_pixels, width, height, diff_path)?;
+	Ok(CaptureDiff {
+		total_pixels: total_pixels,
+		differing_pixels: differing_pixels,
+		max_difference: max_difference,
+		mean_difference: total_difference as f32 / (total_pixels * BYTES_PER_PIXEL).max(1) as f32,
+		diff_image: Some(diff_path.to_path_buf()),
+	})
+}
+
+fn save_pixels(pixels: &mut [u8], width: u32, height: u32, path: &Path) -> Result<(), RealmsError>
+{
+	let pitch = width * BYTES_PER_PIXEL as u32;
+	match Surface::from_data(pixels, width, height, pitch, PixelFormatEnum::RGB24).and_then(|surface| surface.save(path))
+	{
+		Ok(()) => Ok(()),
+		Err(error_info) => Err(RealmsError::CaptureFrameError(error_info)),
+	}
+}
@@ -0,0 +1,104 @@
+use sdl2::pixels::{Color as SdlColor, PixelFormatEnum};
+use sdl2::rect::Rect as SdlRect;
+use sdl2::render::TextureCreator as SdlTextureCreator;
+use sdl2::video::WindowContext as SdlWindowContext;
+
+use crate::blend::{self, BlendMode};
+use crate::sprite::{Sprite, Texture};
+use crate::window::Window;
+use crate::RealmsError;
+
+/// An offscreen texture that sprites are stamped into, for marks that stay on the world such as bullet holes,
+/// scorch marks and paint splats. Each stamp is drawn into the texture once, so any number of decals costs the
+/// same to draw as a single sprite the size of the layer.
+///
+/// The layer covers the area from `(x, y)` to `(x + width, y + height)` in world coordinates. Draw it after the
+/// ground it sits on and before anything that should appear on top of it.
+pub struct DecalLayer<'a>
+{
+	pub x: i32,
+	pub y: i32,
+	texture: Texture<'a>,
+}
+
+impl<'a> DecalLayer<'a>
+{
+	/// Creates an empty (fully transparent) layer. Fails if the renderer cannot draw into textures, or if
+	/// `width` or `height` is larger than the renderer's maximum texture size.
+	pub fn new(window: &mut Window, texture_creator: &'a SdlTextureCreator<SdlWindowContext>, width: u32, height: u32) -> Result<DecalLayer<'a>, RealmsError>
+	{
+		let capabilities = window.get_capabilities();
+		if !capabilities.render_to_texture
+		{
+			return Err(RealmsError::CreateTextureError(format!("the {} renderer cannot draw into textures, which decal layers need", capabilities.renderer_name)));
+		}
+		if !capabilities.supports_texture_size(width, height)
+		{
+			return Err(RealmsError::CreateTextureError(format!("a {}x{} decal layer is larger than the renderer's maximum texture size of {}x{}", width, height, capabilities.max_texture_width, capabilities.max_texture_height)));
+		}
+
+		let sdl_texture = match texture_creator.create_texture_target(PixelFormatEnum::ARGB8888, width, height)
+		{
+			Ok(sdl_texture) => sdl_texture,
+			Err(error_info) => return Err(RealmsError::CreateTextureError(error_info.to_string())),
+		};
+		let mut layer = DecalLayer {
+			x: 0,
+			y: 0,
+			texture: Texture::from_sdl_texture(sdl_texture),
+		};
+		blend::set_texture_blend_mode(&layer.texture, &BlendMode::Alpha)?;
+		// A new target texture's contents are undefined, so it is cleared before anything is stamped into it.
+		layer.clear(window)?;
+		Ok(layer)
+	}
+
+	pub fn get_width(&self) -> u32
+	{
+		self.texture.width
+	}
+
+	pub fn get_height(&self) -> u32
+	{
+		self.texture.height
+	}
+
+	/// Draws `sprite` into the layer at its world position, with its rotation, flip, tint and blend mode.
+	/// Parts of the sprite outside the layer are cut off.
+	pub fn stamp(&mut self, window: &mut Window, sprite: &Sprite) -> Result<(), RealmsError>
+	{
+		let (x, y) = (sprite.x - self.x, sprite.y - self.y);
+		let mut result = Ok(());
+		if let Err(error_info) = window.sdl.canvas.with_texture_canvas(&mut self.texture.sdl_texture, |canvas| {
+			result = sprite.copy_to(canvas, x, y);
+		})
+		{
+			return Err(RealmsError::StampDecalError(error_info.to_string()));
+		}
+		result
+	}
+
+	/// Removes every decal.
+	pub fn clear(&mut self, window: &mut Window) -> Result<(), RealmsError>
+	{
+		match window.sdl.canvas.with_texture_canvas(&mut self.texture.sdl_texture, |canvas| {
+			canvas.set_draw_color(SdlColor::RGBA(0, 0, 0, 0));
+			canvas.clear();
+		})
+		{
+			Ok(()) => Ok(()),
+			Err(error_info) => Err(RealmsError::StampDecalError(error_info.to_string())),
+		}
+	}
+
+	/// Draws the layer as seen by a camera whose top-left corner is at `(camera_x, camera_y)` in the world.
+	pub fn draw(&self, window: &mut Window, camera_x: i32, camera_y: i32) -> Result<(), RealmsError>
+	{
+		let dest = SdlRect::new(self.x - camera_x, self.y - camera_y, self.texture.width, self.texture.height);
+		match window.sdl.canvas.copy(&self.texture.sdl_texture, None, dest)
+		{
+			Ok(()) => Ok(()),
+			Err(error_info) => Err(RealmsError::DrawSpriteError(error_info)),
+		}
+	}
+}
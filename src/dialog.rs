@@ -0,0 +1,36 @@
+use sdl2::messagebox::{show_simple_message_box, MessageBoxFlag};
+
+use crate::window::Window;
+use crate::RealmsError;
+
+pub enum MessageBoxKind
+{
+	Information,
+	Warning,
+	Error,
+}
+
+impl MessageBoxKind
+{
+	pub fn to_sdl(&self) -> MessageBoxFlag
+	{
+		match self
+		{
+			MessageBoxKind::Information => MessageBoxFlag::INFORMATION,
+			MessageBoxKind::Warning => MessageBoxFlag::WARNING,
+			MessageBoxKind::Error => MessageBoxFlag::ERROR,
+		}
+	}
+}
+
+/// Shows a native message box with an OK button, blocking until it is closed.
+/// If a `window` is given, the message box is shown on top of it.
+pub fn message_box(kind: MessageBoxKind, title: &str, message: &str, window: Option<&Window>) -> Result<(), RealmsError>
+{
+	let sdl_window = window.map(|window| window.sdl.canvas.window());
+	match show_simple_message_box(kind.to_sdl(), title, message, sdl_window)
+	{
+		Ok(()) => Ok(()),
+		Err(error_info) => Err(RealmsError::ShowMessageBoxError(error_info.to_string())),
+	}
+}
@@ -0,0 +1,43 @@
+use sdl2::video::DisplayMode as SdlDisplayMode;
+
+#[derive(Clone, PartialEq)]
+pub struct VideoMode
+{
+	pub width: u32,
+	pub height: u32,
+	pub refresh_rate: i32,
+}
+
+impl VideoMode
+{
+	pub fn from_sdl(sdl_display_mode: &SdlDisplayMode) -> VideoMode
+	{
+		VideoMode {
+			width: sdl_display_mode.w as u32,
+			height: sdl_display_mode.h as u32,
+			refresh_rate: sdl_display_mode.refresh_rate,
+		}
+	}
+}
+
+#[derive(Clone, PartialEq)]
+pub enum DisplayMode
+{
+	Windowed,
+	/// Fullscreen at the desktop resolution, without changing the monitor's video mode.
+	Borderless,
+	/// Fullscreen with the monitor switched to the given video mode. It should be one returned by `window.get_video_modes()`.
+	Exclusive(VideoMode),
+}
+
+pub struct Monitor
+{
+	pub index: i32,
+	pub name: String,
+	/// Position of the monitor's top-left corner on the desktop.
+	pub x: i32,
+	pub y: i32,
+	pub width: u32,
+	pub height: u32,
+	pub video_mode: VideoMode,
+}
@@ -10,6 +10,25 @@ pub enum RealmsError
 	DrawRectError(String),
 	LoadTextureError(String),
 	DrawSpriteError(String),
+	SetBlendModeError(String),
+	RequestAttentionError(String),
+	GetDisplayModeError(String),
+	CreateTextureError(String),
+	ShowMessageBoxError(String),
+	SetWindowSizeError(String),
+	SetResizePolicyError(String),
+	SetDisplayModeError(String),
+	SetGammaError(String),
+	GetMonitorError(String),
+	CaptureFrameError(String),
+	SetOpacityError(String),
+	SetVsyncError(String),
+	CreateSdlControllerSubsystemError(String),
+	AddControllerMappingError(String),
+	SetTitleError(String),
+	ParseDiceError(String),
+	DrawTrailError(String),
+	StampDecalError(String),
 }
 
 impl RealmsError
@@ -26,6 +45,25 @@ impl RealmsError
 			RealmsError::DrawRectError(info) => info,
 			RealmsError::LoadTextureError(info) => info,
 			RealmsError::DrawSpriteError(info) => info,
+			RealmsError::SetBlendModeError(info) => info,
+			RealmsError::RequestAttentionError(info) => info,
+			RealmsError::GetDisplayModeError(info) => info,
+			RealmsError::CreateTextureError(info) => info,
+			RealmsError::ShowMessageBoxError(info) => info,
+			RealmsError::SetWindowSizeError(info) => info,
+			RealmsError::SetResizePolicyError(info) => info,
+			RealmsError::SetDisplayModeError(info) => info,
+			RealmsError::SetGammaError(info) => info,
+			RealmsError::GetMonitorError(info) => info,
+			RealmsError::CaptureFrameError(info) => info,
+			RealmsError::SetOpacityError(info) => info,
+			RealmsError::SetVsyncError(info) => info,
+			RealmsError::CreateSdlControllerSubsystemError(info) => info,
+			RealmsError::AddControllerMappingError(info) => info,
+			RealmsError::SetTitleError(info) => info,
+			RealmsError::ParseDiceError(info) => info,
+			RealmsError::DrawTrailError(info) => info,
+			RealmsError::StampDecalError(info) => info,
 		}
 	}
 }
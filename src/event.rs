@@ -1,6 +1,7 @@
 use sdl2::controller::{Axis as SdlControllerAxis, Button as SdlControllerButton};
-use sdl2::event::Event as SdlEvent;
+use sdl2::event::{DisplayEvent as SdlDisplayEvent, Event as SdlEvent, WindowEvent as SdlWindowEvent};
 use sdl2::mouse::MouseButton as SdlMouseButton;
+use std::time::Duration;
 
 
 pub enum MouseButton
@@ -112,6 +113,18 @@ pub struct ControllerAxisMotionEvent
 	pub value: i16,
 }
 
+pub struct MonitorEvent
+{
+	/// The index of the monitor, matching `Monitor::index`.
+	pub index: i32,
+}
+
+pub struct ContentScaleEvent
+{
+	pub x: f32,
+	pub y: f32,
+}
+
 pub struct ControllerButtonEvent
 {
 	pub device: u32,
@@ -138,6 +151,44 @@ pub enum Event
 	ControllerAxisMotion(ControllerAxisMotionEvent),
 	ControllerButtonDown(ControllerButtonEvent),
 	ControllerButtonUp(ControllerButtonEvent),
+	/// Render target textures were reset and their contents must be drawn again.
+	RenderTargetsReset,
+	/// The render device was reset (e.g. after a driver update). All `Texture`s must be loaded again.
+	RenderDeviceReset,
+	/// The window was minimized, by the user or with `window.iconify()`. Games usually pause until `Restore`.
+	Iconify,
+	Maximize,
+	/// The window was restored from being minimized or maximized.
+	Restore,
+	/// A monitor was connected. The event's `index` matches `Monitor::index` from `window.get_monitors()`.
+	MonitorConnected(MonitorEvent),
+	MonitorDisconnected(MonitorEvent),
+	/// The window moved to a display with a different pixel density. Detected by `window.get_events()`.
+	ContentScaleChanged(ContentScaleEvent),
+}
+
+/// An `Event` together with when it happened.
+/// Times are measured from when the window was built, on the same clock for every event.
+pub struct TimedEvent
+{
+	pub event: Event,
+	/// When the input happened, as recorded by SDL. Events Realms detects itself, such as
+	/// `ContentScaleChanged`, are timestamped when they are detected.
+	pub timestamp: Duration,
+	/// When the event was polled by `window.get_timed_events()`. See `queue_delay()` for how long the event
+	/// waited in the queue.
+	pub polled_at: Duration,
+	/// The number of frames drawn before the event was polled.
+	pub frame: u64,
+}
+
+impl TimedEvent
+{
+	/// How long the event waited in the queue before it was polled. Close to zero for events Realms detects itself.
+	pub fn queue_delay(&self) -> Duration
+	{
+		self.polled_at.saturating_sub(self.timestamp)
+	}
 }
 
 impl Event
@@ -243,6 +294,38 @@ impl Event
 					})
 				},
 
+				SdlEvent::RenderTargetsReset { .. } => {
+					Event::RenderTargetsReset
+				},
+
+				SdlEvent::RenderDeviceReset { .. } => {
+					Event::RenderDeviceReset
+				},
+
+				SdlEvent::Window { win_event: SdlWindowEvent::Minimized, .. } => {
+					Event::Iconify
+				},
+
+				SdlEvent::Window { win_event: SdlWindowEvent::Maximized, .. } => {
+					Event::Maximize
+				},
+
+				SdlEvent::Window { win_event: SdlWindowEvent::Restored, .. } => {
+					Event::Restore
+				},
+
+				SdlEvent::Display { display_index, display_event: SdlDisplayEvent::Connected, .. } => {
+					Event::MonitorConnected(MonitorEvent {
+						index: display_index,
+					})
+				},
+
+				SdlEvent::Display { display_index, display_event: SdlDisplayEvent::Disconnected, .. } => {
+					Event::MonitorDisconnected(MonitorEvent {
+						index: display_index,
+					})
+				},
+
 				_ => Event::None
 			}
 	}
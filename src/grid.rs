@@ -0,0 +1,218 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::data;
+use crate::math::Vec2;
+
+/// The position of a cell in a grid, counted in cells rather than pixels.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Cell
+{
+	pub x: i32,
+	pub y: i32,
+}
+
+/// Which cells count as touching a cell.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Neighbourhood
+{
+	/// Up, down, left and right.
+	Four,
+	/// `Four`, plus the diagonals.
+	Eight,
+}
+
+const FOUR_OFFSETS: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const EIGHT_OFFSETS: [(i32, i32); 8] = [(0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)];
+
+impl Cell
+{
+	pub fn new(x: i32, y: i32) -> Cell
+	{
+		Cell {
+			x: x,
+			y: y,
+		}
+	}
+
+	/// The cells touching this one, clockwise starting from the cell above.
+	pub fn neighbours(&self, neighbourhood: Neighbourhood) -> impl Iterator<Item = Cell>
+	{
+		let offsets: &'static [(i32, i32)] = match neighbourhood
+		{
+			Neighbourhood::Four => &FOUR_OFFSETS,
+			Neighbourhood::Eight => &EIGHT_OFFSETS,
+		};
+		let cell = *self;
+		offsets.iter().map(move |(offset_x, offset_y)| Cell::new(cell.x + offset_x, cell.y + offset_y))
+	}
+
+	/// The number of steps between the cells when moving only up, down, left and right.
+	pub fn manhattan_distance(&self, other: Cell) -> i32
+	{
+		(self.x - other.x).abs() + (self.y - other.y).abs()
+	}
+
+	/// The number of steps between the cells when diagonal moves are allowed.
+	pub fn chebyshev_distance(&self, other: Cell) -> i32
+	{
+		(self.x - other.x).abs().max((self.y - other.y).abs())
+	}
+}
+
+/// Converts between world positions (in pixels) and cells of a fixed size, with cell `(0, 0)` at `origin`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Grid
+{
+	pub cell_width: f32,
+	pub cell_height: f32,
+	pub origin: Vec2,
+}
+
+impl Grid
+{
+	pub fn new(cell_width: f32, cell_height: f32) -> Grid
+	{
+		Grid {
+			cell_width: cell_width,
+			cell_height: cell_height,
+			origin: Vec2::ZERO,
+		}
+	}
+
+	/// The cell containing `position`. Positions on a cell's top or left edge belong to that cell.
+	pub fn world_to_cell(&self, position: Vec2) -> Cell
+	{
+		Cell::new(
+			((position.x - self.origin.x) / self.cell_width).floor() as i32,
+			((position.y - self.origin.y) / self.cell_height).floor() as i32,
+		)
+	}
+
+	/// The top-left corner of `cell`.
+	pub fn cell_to_world(&self, cell: Cell) -> Vec2
+	{
+		Vec2::new(self.origin.x + cell.x as f32 * self.cell_width, self.origin.y + cell.y as f32 * self.cell_height)
+	}
+
+	pub fn cell_center(&self, cell: Cell) -> Vec2
+	{
+		self.cell_to_world(cell) + Vec2::new(self.cell_width / 2.0, self.cell_height / 2.0)
+	}
+
+	pub fn cell_bounds(&self, cell: Cell) -> data::Rect
+	{
+		let corner = self.cell_to_world(cell);
+		data::Rect::new(corner.x, corner.y, self.cell_width, self.cell_height)
+	}
+}
+
+/// Every cell on the straight line from `from` to `to`, including both ends, using Bresenham's algorithm.
+/// Consecutive cells always touch, possibly only diagonally.
+pub fn line(from: Cell, to: Cell) -> Vec<Cell>
+{
+	let delta_x = (to.x - from.x).abs();
+	let delta_y = -(to.y - from.y).abs();
+	let step_x = (to.x - from.x).signum();
+	let step_y = (to.y - from.y).signum();
+
+	let mut cells: Vec<Cell> = Vec::with_capacity(delta_x.max(-delta_y) as usize + 1);
+	let mut cell = from;
+	let mut error = delta_x + delta_y;
+	loop
+	{
+		cells.push(cell);
+		if cell == to
+			{ break }
+		let doubled_error = error * 2;
+		if doubled_error >= delta_y
+		{
+			error += delta_y;
+			cell.x += step_x;
+		}
+		if doubled_error <= delta_x
+		{
+			error += delta_x;
+			cell.y += step_y;
+		}
+	}
+	cells
+}
+
+/// Whether `to` can be seen from `from`: `true` unless a cell on the `line` between them is blocked.
+/// The two end cells themselves are never checked, so a wall can see the cell next to it.
+pub fn line_of_sight(from: Cell, to: Cell, is_blocked: impl Fn(Cell) -> bool) -> bool
+{
+	let cells = line(from, to);
+	let between = &cells[1..cells.len().saturating_sub(1).max(1)];
+	!between.iter().any(|cell| is_blocked(*cell))
+}
+
+/// Every cell connected to `start` through cells for which `can_fill` returns `true`, in order of distance from
+/// `start` (measured in steps). `start` is only included if `can_fill` accepts it.
+///
+/// `can_fill` must reject cells outside your map, otherwise the fill never ends on an open grid.
+pub fn flood_fill(start: Cell, neighbourhood: Neighbourhood, mut can_fill: impl FnMut(Cell) -> bool) -> Vec<Cell>
+{
+	let mut filled: Vec<Cell> = Vec::new();
+	if !can_fill(start)
+		{ return filled }
+
+	let mut visited: HashSet<Cell> = HashSet::new();
+	let mut queue: VecDeque<Cell> = VecDeque::new();
+	visited.insert(start);
+	queue.push_back(start);
+	while let Some(cell) = queue.pop_front()
+	{
+		filled.push(cell);
+		for neighbour in cell.neighbours(neighbourhood)
+		{
+			if visited.insert(neighbour) && can_fill(neighbour)
+			{
+				queue.push_back(neighbour);
+			}
+		}
+	}
+	filled
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn cells(points: &[(i32, i32)]) -> Vec<Cell>
+	{
+		points.iter().map(|(x, y)| Cell::new(*x, *y)).collect()
+	}
+
+	#[test]
+	fn steep_line_steps_once_per_row()
+	{
+		assert_eq!(line(Cell::new(0, 0), Cell::new(2, 5)), cells(&[(0, 0), (0, 1), (1, 2), (1, 3), (2, 4), (2, 5)]));
+	}
+
+	#[test]
+	fn negative_slope_line()
+	{
+		assert_eq!(line(Cell::new(0, 0), Cell::new(4, -2)), cells(&[(0, 0), (1, -1), (2, -1), (3, -2), (4, -2)]));
+		assert_eq!(line(Cell::new(4, -2), Cell::new(0, 0)).len(), 5);
+	}
+
+	#[test]
+	fn flood_fill_stops_at_the_grid_edge()
+	{
+		let in_bounds = |cell: Cell| (0..3).contains(&cell.x) && (0..3).contains(&cell.y);
+		let filled = flood_fill(Cell::new(0, 0), Neighbourhood::Eight, in_bounds);
+		assert_eq!(filled.len(), 9);
+		assert_eq!(filled[0], Cell::new(0, 0));
+		assert!(filled.iter().all(|cell| in_bounds(*cell)));
+	}
+
+	#[test]
+	fn flood_fill_stops_at_walls()
+	{
+		let filled = flood_fill(Cell::new(0, 0), Neighbourhood::Four, |cell| (0..4).contains(&cell.x) && (0..3).contains(&cell.y) && cell.x != 2);
+		assert_eq!(filled.len(), 6);
+		assert!(filled.iter().all(|cell| cell.x < 2));
+	}
+}
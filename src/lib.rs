@@ -4,6 +4,26 @@ pub mod event;
 pub mod shape;
 pub mod error;
 pub mod sprite;
+pub mod parallax;
+pub mod blend;
+pub mod prelude;
+pub mod time;
+pub mod render;
+pub mod assets;
+pub mod crash;
+pub mod dialog;
+pub mod anim;
+pub mod snapshot;
+pub mod testing;
+pub mod display;
+pub mod debug;
+pub mod math;
+pub mod data;
+pub mod grid;
+pub mod procgen;
+pub mod daynight;
+pub mod trail;
+pub mod decal;
 
 pub use colour::Colour;
 pub use error::RealmsError;
@@ -0,0 +1,356 @@
+use std::f32::consts::{PI, TAU};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::anim::Lerp;
+
+/// An angle, stored in radians. Positive angles turn clockwise on screen, since the y axis points down.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+pub struct Angle
+{
+	pub radians: f32,
+}
+
+impl Angle
+{
+	pub const ZERO: Angle = Angle { radians: 0.0 };
+
+	pub fn from_radians(radians: f32) -> Angle
+	{
+		Angle {
+			radians: radians,
+		}
+	}
+
+	pub fn from_degrees(degrees: f32) -> Angle
+	{
+		Angle {
+			radians: degrees.to_radians(),
+		}
+	}
+
+	pub fn degrees(&self) -> f32
+	{
+		self.radians.to_degrees()
+	}
+
+	/// The same angle in the range `-PI..=PI`.
+	pub fn wrapped(&self) -> Angle
+	{
+		let wrapped = (self.radians + PI).rem_euclid(TAU) - PI;
+		Angle::from_radians(if wrapped == -PI { PI } else { wrapped })
+	}
+
+	/// The same angle in the range `0..TAU`.
+	pub fn wrapped_positive(&self) -> Angle
+	{
+		Angle::from_radians(self.radians.rem_euclid(TAU))
+	}
+
+	/// The smallest signed turn from this angle to `other`, in the range `-PI..=PI`.
+	pub fn shortest_to(&self, other: Angle) -> Angle
+	{
+		Angle::from_radians(other.radians - self.radians).wrapped()
+	}
+
+	pub fn sin(&self) -> f32
+	{
+		self.radians.sin()
+	}
+
+	pub fn cos(&self) -> f32
+	{
+		self.radians.cos()
+	}
+}
+
+impl Lerp for Angle
+{
+	/// Turns the short way around, so lerping from 350° to 10° passes through 0° rather than 180°.
+	fn lerp(&self, other: &Angle, t: f32) -> Angle
+	{
+		Angle::from_radians(self.radians + self.shortest_to(*other).radians * t)
+	}
+}
+
+impl Add for Angle
+{
+	type Output = Angle;
+
+	fn add(self, other: Angle) -> Angle
+	{
+		Angle::from_radians(self.radians + other.radians)
+	}
+}
+
+impl Sub for Angle
+{
+	type Output = Angle;
+
+	fn sub(self, other: Angle) -> Angle
+	{
+		Angle::from_radians(self.radians - other.radians)
+	}
+}
+
+impl Neg for Angle
+{
+	type Output = Angle;
+
+	fn neg(self) -> Angle
+	{
+		Angle::from_radians(-self.radians)
+	}
+}
+
+impl Mul<f32> for Angle
+{
+	type Output = Angle;
+
+	fn mul(self, scale: f32) -> Angle
+	{
+		Angle::from_radians(self.radians * scale)
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Vec2
+{
+	pub x: f32,
+	pub y: f32,
+}
+
+impl Vec2
+{
+	pub const ZERO: Vec2 = Vec2 { x: 0.0, y: 0.0 };
+
+	pub fn new(x: f32, y: f32) -> Vec2
+	{
+		Vec2 {
+			x: x,
+			y: y,
+		}
+	}
+
+	/// A unit vector pointing in the direction of `angle`, where `Angle::ZERO` points right.
+	pub fn from_angle(angle: Angle) -> Vec2
+	{
+		Vec2::new(angle.cos(), angle.sin())
+	}
+
+	/// The direction this vector points in.
+	pub fn angle(&self) -> Angle
+	{
+		Angle::from_radians(self.y.atan2(self.x))
+	}
+
+	/// The direction from this point to `target`.
+	pub fn angle_to(&self, target: Vec2) -> Angle
+	{
+		(target - *self).angle()
+	}
+
+	pub fn length(&self) -> f32
+	{
+		self.x.hypot(self.y)
+	}
+
+	pub fn distance_to(&self, other: Vec2) -> f32
+	{
+		(other - *self).length()
+	}
+
+	/// A vector with the same direction and a length of 1, or `Vec2::ZERO` if this vector has no length.
+	pub fn normalized(&self) -> Vec2
+	{
+		let length = self.length();
+		match length > 0.0
+		{
+			true => Vec2::new(self.x / length, self.y / length),
+			false => Vec2::ZERO,
+		}
+	}
+
+	pub fn dot(&self, other: Vec2) -> f32
+	{
+		self.x * other.x + self.y * other.y
+	}
+
+	pub fn rotated(&self, angle: Angle) -> Vec2
+	{
+		Mat2::from_angle(angle) * *self
+	}
+
+	/// Rotates this point around `origin`.
+	pub fn rotated_around(&self, origin: Vec2, angle: Angle) -> Vec2
+	{
+		origin + (*self - origin).rotated(angle)
+	}
+}
+
+impl Lerp for Vec2
+{
+	fn lerp(&self, other: &Vec2, t: f32) -> Vec2
+	{
+		Vec2::new(self.x.lerp(&other.x, t), self.y.lerp(&other.y, t))
+	}
+}
+
+impl Add for Vec2
+{
+	type Output = Vec2;
+
+	fn add(self, other: Vec2) -> Vec2
+	{
+		Vec2::new(self.x + other.x, self.y + other.y)
+	}
+}
+
+impl AddAssign for Vec2
+{
+	fn add_assign(&mut self, other: Vec2)
+	{
+		*self = *self + other;
+	}
+}
+
+impl Sub for Vec2
+{
+	type Output = Vec2;
+
+	fn sub(self, other: Vec2) -> Vec2
+	{
+		Vec2::new(self.x - other.x, self.y - other.y)
+	}
+}
+
+impl SubAssign for Vec2
+{
+	fn sub_assign(&mut self, other: Vec2)
+	{
+		*self = *self - other;
+	}
+}
+
+impl Neg for Vec2
+{
+	type Output = Vec2;
+
+	fn neg(self) -> Vec2
+	{
+		Vec2::new(-self.x, -self.y)
+	}
+}
+
+impl Mul<f32> for Vec2
+{
+	type Output = Vec2;
+
+	fn mul(self, scale: f32) -> Vec2
+	{
+		Vec2::new(self.x * scale, self.y * scale)
+	}
+}
+
+/// A 2x2 matrix, stored row by row.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Mat2
+{
+	pub rows: [[f32; 2]; 2],
+}
+
+impl Mat2
+{
+	pub const IDENTITY: Mat2 = Mat2 { rows: [[1.0, 0.0], [0.0, 1.0]] };
+
+	pub fn from_angle(angle: Angle) -> Mat2
+	{
+		let (sin, cos) = angle.radians.sin_cos();
+		Mat2 {
+			rows: [[cos, -sin], [sin, cos]],
+		}
+	}
+}
+
+impl Mul<Vec2> for Mat2
+{
+	type Output = Vec2;
+
+	fn mul(self, vector: Vec2) -> Vec2
+	{
+		Vec2::new(
+			self.rows[0][0] * vector.x + self.rows[0][1] * vector.y,
+			self.rows[1][0] * vector.x + self.rows[1][1] * vector.y,
+		)
+	}
+}
+
+impl Mul for Mat2
+{
+	type Output = Mat2;
+
+	fn mul(self, other: Mat2) -> Mat2
+	{
+		let mut rows = [[0.0; 2]; 2];
+		for (row, row_values) in rows.iter_mut().enumerate()
+		{
+			for (column, value) in row_values.iter_mut().enumerate()
+			{
+				*value = self.rows[row][0] * other.rows[0][column] + self.rows[row][1] * other.rows[1][column];
+			}
+		}
+		Mat2 {
+			rows: rows,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn assert_degrees(angle: Angle, degrees: f32)
+	{
+		assert!((angle.degrees() - degrees).abs() < 0.01, "expected {}°, got {}°", degrees, angle.degrees());
+	}
+
+	#[test]
+	fn wrapped_stays_within_half_a_turn()
+	{
+		assert_degrees(Angle::from_degrees(270.0).wrapped(), -90.0);
+		assert_degrees(Angle::from_degrees(-190.0).wrapped(), 170.0);
+		assert_degrees(Angle::from_degrees(720.0 + 45.0).wrapped(), 45.0);
+	}
+
+	#[test]
+	fn wrapped_half_turn_is_positive()
+	{
+		assert_degrees(Angle::from_degrees(180.0).wrapped(), 180.0);
+		assert_degrees(Angle::from_degrees(-180.0).wrapped(), 180.0);
+	}
+
+	#[test]
+	fn shortest_to_crosses_the_half_turn()
+	{
+		assert_degrees(Angle::from_degrees(170.0).shortest_to(Angle::from_degrees(-170.0)), 20.0);
+		assert_degrees(Angle::from_degrees(-170.0).shortest_to(Angle::from_degrees(170.0)), -20.0);
+	}
+
+	#[test]
+	fn lerp_turns_the_short_way_across_the_half_turn()
+	{
+		let from = Angle::from_degrees(170.0);
+		let to = Angle::from_degrees(-170.0);
+		assert_degrees(from.lerp(&to, 0.5).wrapped(), 180.0);
+		assert_degrees(from.lerp(&to, 0.25).wrapped(), 175.0);
+		assert_degrees(from.lerp(&to, 1.0).wrapped(), -170.0);
+	}
+
+	#[test]
+	fn lerp_turns_the_short_way_across_zero()
+	{
+		let from = Angle::from_degrees(350.0);
+		let to = Angle::from_degrees(10.0);
+		assert_degrees(from.lerp(&to, 0.5).wrapped(), 0.0);
+	}
+}
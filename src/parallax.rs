@@ -0,0 +1,111 @@
+use sdl2::rect::Rect as SdlRect;
+
+use crate::sprite::Texture;
+use crate::window::Window;
+use crate::RealmsError;
+
+pub enum RepeatMode
+{
+	None,
+	Horizontal,
+	Vertical,
+	Both,
+}
+
+pub struct ParallaxLayer<'a>
+{
+	pub texture: &'a Texture<'a>,
+	pub scroll_factor: f32,
+	pub repeat: RepeatMode,
+}
+
+impl<'a> ParallaxLayer<'a>
+{
+	/// A `scroll_factor` of `0.0` keeps the layer fixed to the screen, `1.0` moves it with the camera.
+	pub fn new(texture: &'a Texture, scroll_factor: f32, repeat: RepeatMode) -> ParallaxLayer<'a>
+	{
+		ParallaxLayer {
+			texture: texture,
+			scroll_factor: scroll_factor,
+			repeat: repeat,
+		}
+	}
+
+	pub fn draw(&self, window: &mut Window, camera_x: i32, camera_y: i32) -> Result<(), RealmsError>
+	{
+		let width = self.texture.width as i32;
+		let height = self.texture.height as i32;
+		if width == 0 || height == 0
+			{ return Ok(()) }
+
+		let offset_x = -((camera_x as f32 * self.scroll_factor) as i32);
+		let offset_y = -((camera_y as f32 * self.scroll_factor) as i32);
+
+		let (start_x, end_x) = match self.repeat
+		{
+			RepeatMode::Horizontal | RepeatMode::Both => (offset_x.rem_euclid(width) - width, window.width as i32),
+			_ => (offset_x, offset_x + 1),
+		};
+		let (start_y, end_y) = match self.repeat
+		{
+			RepeatMode::Vertical | RepeatMode::Both => (offset_y.rem_euclid(height) - height, window.height as i32),
+			_ => (offset_y, offset_y + 1),
+		};
+
+		let mut y = start_y;
+		while y < end_y
+		{
+			let mut x = start_x;
+			while x < end_x
+			{
+				let dest = SdlRect::new(x, y, self.texture.width, self.texture.height);
+				if let Err(error_info) = window.sdl.canvas.copy(&self.texture.sdl_texture, None, dest)
+				{
+					return Err(RealmsError::DrawSpriteError(error_info));
+				}
+				x += width;
+			}
+			y += height;
+		}
+
+		Ok(())
+	}
+}
+
+pub struct ParallaxLayers<'a>
+{
+	pub layers: Vec<ParallaxLayer<'a>>,
+}
+
+impl<'a> ParallaxLayers<'a>
+{
+	pub fn new() -> ParallaxLayers<'a>
+	{
+		ParallaxLayers {
+			layers: Vec::new(),
+		}
+	}
+
+	/// Layers are drawn in the order they are added, so add the furthest layer first.
+	pub fn add(&mut self, layer: ParallaxLayer<'a>)
+	{
+		self.layers.push(layer);
+	}
+
+	pub fn draw(&self, window: &mut Window, camera_x: i32, camera_y: i32) -> Result<(), RealmsError>
+	{
+		for layer in &self.layers
+		{
+			layer.draw(window, camera_x, camera_y)?;
+		}
+		Ok(())
+	}
+}
+
+impl<'a> Default for ParallaxLayers<'a>
+{
+	fn default() -> ParallaxLayers<'a>
+	{
+		ParallaxLayers::new()
+	}
+}
@@ -0,0 +1,11 @@
+pub use crate::blend::BlendMode;
+pub use crate::colour::Colour;
+pub use crate::display::{DisplayMode, Monitor, VideoMode};
+pub use crate::error::RealmsError;
+pub use crate::event::{ControllerButton, Event, MouseButton};
+pub use crate::math::{Angle, Vec2};
+pub use crate::parallax::{ParallaxLayer, ParallaxLayers, RepeatMode};
+pub use crate::shape::Rect;
+pub use crate::sprite::{Sprite, Texture};
+pub use crate::time::GameTime;
+pub use crate::window::Window;
@@ -0,0 +1,582 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::anim::Lerp;
+use crate::grid::{Cell, Neighbourhood};
+use crate::RealmsError;
+
+/// A small, fast random number generator (SplitMix64). The same seed always produces the same numbers on every
+/// platform, so a seed is enough to regenerate a level or replay a loot roll.
+///
+/// It is not suitable for anything security related.
+#[derive(Clone, Debug)]
+pub struct Rng
+{
+	state: u64,
+}
+
+impl Rng
+{
+	pub fn new(seed: u64) -> Rng
+	{
+		Rng {
+			state: seed,
+		}
+	}
+
+	/// Seeds the generator from the current time, for when results don't need to be reproducible.
+	pub fn from_time() -> Rng
+	{
+		let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_nanos() as u64).unwrap_or(0);
+		Rng::new(nanos)
+	}
+
+	pub fn next_u64(&mut self) -> u64
+	{
+		self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+		let mut value = self.state;
+		value = (value ^ (value >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+		value = (value ^ (value >> 27)).wrapping_mul(0x94D049BB133111EB);
+		value ^ (value >> 31)
+	}
+
+	/// A number from `0.0` up to, but not including, `1.0`.
+	pub fn next_f32(&mut self) -> f32
+	{
+		(self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+	}
+
+	/// A number from `0` up to, but not including, `max`. Returns `0` if `max` is `0`.
+	pub fn below(&mut self, max: u64) -> u64
+	{
+		match max
+		{
+			0 => 0,
+			_ => ((self.next_u64() as u128 * max as u128) >> 64) as u64,
+		}
+	}
+
+	/// A number from `min` to `max`, including both.
+	pub fn range(&mut self, min: i32, max: i32) -> i32
+	{
+		if max <= min
+			{ return min }
+		let span = (max as i64 - min as i64 + 1) as u64;
+		(min as i64 + self.below(span) as i64) as i32
+	}
+
+	/// `true` with the given probability, from `0.0` (never) to `1.0` (always).
+	pub fn chance(&mut self, probability: f32) -> bool
+	{
+		self.next_f32() < probability
+	}
+}
+
+/// A list of items where each item is picked in proportion to its weight: an item with weight 3 is picked
+/// three times as often as one with weight 1. Useful for loot drops and random encounters.
+#[derive(Clone, Debug)]
+pub struct WeightedTable<T>
+{
+	entries: Vec<(T, u32)>,
+	total_weight: u64,
+}
+
+impl<T> WeightedTable<T>
+{
+	pub fn new() -> WeightedTable<T>
+	{
+		WeightedTable {
+			entries: Vec::new(),
+			total_weight: 0,
+		}
+	}
+
+	/// Items with a weight of `0` are kept in the table but never sampled.
+	pub fn add(&mut self, item: T, weight: u32)
+	{
+		self.total_weight += weight as u64;
+		self.entries.push((item, weight));
+	}
+
+	/// Removes the first entry equal to `item`, returning its weight, or `None` if it is not in the table.
+	pub fn remove(&mut self, item: &T) -> Option<u32>
+	where
+		T: PartialEq,
+	{
+		let index = self.entries.iter().position(|(entry, _)| entry == item)?;
+		let (_, weight) = self.entries.remove(index);
+		self.total_weight -= weight as u64;
+		Some(weight)
+	}
+
+	/// Picks an item at random, or `None` if the table is empty or every weight is `0`.
+	pub fn sample(&self, rng: &mut Rng) -> Option<&T>
+	{
+		let mut remaining = rng.below(self.total_weight);
+		for (item, weight) in &self.entries
+		{
+			if remaining < *weight as u64
+				{ return Some(item) }
+			remaining -= *weight as u64;
+		}
+		None
+	}
+
+	/// The chance of `sample()` picking the entry at `index`, from `0.0` to `1.0`.
+	pub fn probability(&self, index: usize) -> f32
+	{
+		match (self.entries.get(index), self.total_weight)
+		{
+			(Some((_, weight)), total_weight) if total_weight > 0 => *weight as f32 / total_weight as f32,
+			_ => 0.0,
+		}
+	}
+
+	pub fn total_weight(&self) -> u64
+	{
+		self.total_weight
+	}
+
+	pub fn len(&self) -> usize
+	{
+		self.entries.len()
+	}
+
+	pub fn is_empty(&self) -> bool
+	{
+		self.entries.is_empty()
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = &(T, u32)>
+	{
+		self.entries.iter()
+	}
+}
+
+impl<T> Default for WeightedTable<T>
+{
+	fn default() -> WeightedTable<T>
+	{
+		WeightedTable::new()
+	}
+}
+
+/// A dice roll in tabletop notation, such as `"2d6+3"`: roll two six-sided dice and add 3.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Dice
+{
+	pub count: u32,
+	pub sides: u32,
+	pub modifier: i32,
+}
+
+impl Dice
+{
+	pub fn new(count: u32, sides: u32, modifier: i32) -> Dice
+	{
+		Dice {
+			count: count,
+			sides: sides,
+			modifier: modifier,
+		}
+	}
+
+	/// Parses notation like `"d20"`, `"3d8"`, `"2d6+3"` or `"1d4-1"`. Spaces and letter case are ignored.
+	pub fn parse(notation: &str) -> Result<Dice, RealmsError>
+	{
+		let invalid = || RealmsError::ParseDiceError(format!("invalid dice notation: {:?}", notation));
+		let cleaned: String = notation.chars().filter(|character| !character.is_whitespace()).collect::<String>().to_lowercase();
+
+		let (count, rest) = cleaned.split_once('d').ok_or_else(invalid)?;
+		let count: u32 = match count
+		{
+			"" => 1,
+			_ => count.parse().map_err(|_| invalid())?,
+		};
+		let (sides, modifier) = match rest.find(['+', '-'])
+		{
+			Some(index) => (&rest[..index], rest[index..].parse::<i32>().map_err(|_| invalid())?),
+			None => (rest, 0),
+		};
+		let sides: u32 = sides.parse().map_err(|_| invalid())?;
+
+		if count == 0 || sides == 0
+			{ return Err(invalid()) }
+		Ok(Dice::new(count, sides, modifier))
+	}
+
+	pub fn roll(&self, rng: &mut Rng) -> i32
+	{
+		let total: i64 = (0..self.count).map(|_| rng.below(self.sides as u64) as i64 + 1).sum();
+		clamp_to_i32(total + self.modifier as i64)
+	}
+
+	/// The lowest possible roll. Like `roll()`, it is clamped to the range of an `i32`.
+	pub fn min(&self) -> i32
+	{
+		clamp_to_i32(self.count as i64 + self.modifier as i64)
+	}
+
+	/// The highest possible roll. Like `roll()`, it is clamped to the range of an `i32`.
+	pub fn max(&self) -> i32
+	{
+		clamp_to_i32(self.count as i64 * self.sides as i64 + self.modifier as i64)
+	}
+}
+
+fn clamp_to_i32(value: i64) -> i32
+{
+	value.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+}
+
+/// A grid of values, one per cell, usually from `0.0` to `1.0`. Produced by a `Pipeline`.
+#[derive(Clone, Debug)]
+pub struct Heightmap
+{
+	pub width: u32,
+	pub height: u32,
+	values: Vec<f32>,
+}
+
+impl Heightmap
+{
+	pub fn new(width: u32, height: u32) -> Heightmap
+	{
+		Heightmap {
+			width: width,
+			height: height,
+			values: vec![0.0; (width * height) as usize],
+		}
+	}
+
+	pub fn contains(&self, cell: Cell) -> bool
+	{
+		cell.x >= 0 && cell.y >= 0 && (cell.x as u32) < self.width && (cell.y as u32) < self.height
+	}
+
+	/// Returns `None` for cells outside the heightmap.
+	pub fn get(&self, cell: Cell) -> Option<f32>
+	{
+		match self.contains(cell)
+		{
+			true => Some(self.values[self.index(cell)]),
+			false => None,
+		}
+	}
+
+	/// Does nothing for cells outside the heightmap.
+	pub fn set(&mut self, cell: Cell, value: f32)
+	{
+		if self.contains(cell)
+		{
+			let index = self.index(cell);
+			self.values[index] = value;
+		}
+	}
+
+	pub fn cells(&self) -> impl Iterator<Item = Cell>
+	{
+		let width = self.width as i32;
+		(0..self.height as i32).flat_map(move |y| (0..width).map(move |x| Cell::new(x, y)))
+	}
+
+	/// Converts every value into a tile with `biomes`.
+	pub fn to_tilemap<T: Clone>(&self, biomes: &Biomes<T>) -> Tilemap<T>
+	{
+		Tilemap {
+			width: self.width,
+			height: self.height,
+			tiles: self.values.iter().map(|value| biomes.get(*value).clone()).collect(),
+		}
+	}
+
+	fn index(&self, cell: Cell) -> usize
+	{
+		cell.y as usize * self.width as usize + cell.x as usize
+	}
+}
+
+/// Maps heightmap values to tiles: each value becomes the tile of the first biome whose upper limit it is below,
+/// or the fallback tile if it is above all of them.
+#[derive(Clone, Debug)]
+pub struct Biomes<T>
+{
+	limits: Vec<(f32, T)>,
+	fallback: T,
+}
+
+impl<T> Biomes<T>
+{
+	pub fn new(fallback: T) -> Biomes<T>
+	{
+		Biomes {
+			limits: Vec::new(),
+			fallback: fallback,
+		}
+	}
+
+	/// Values below `upper_limit` (and not claimed by an earlier biome) become `tile`.
+	/// Add biomes from the lowest limit to the highest.
+	pub fn add(mut self, upper_limit: f32, tile: T) -> Biomes<T>
+	{
+		self.limits.push((upper_limit, tile));
+		self
+	}
+
+	pub fn get(&self, value: f32) -> &T
+	{
+		self.limits.iter()
+			.find(|(upper_limit, _)| value < *upper_limit)
+			.map(|(_, tile)| tile)
+			.unwrap_or(&self.fallback)
+	}
+}
+
+/// A grid of tiles, such as walls and floors, produced by `Heightmap::to_tilemap()`.
+#[derive(Clone, Debug)]
+pub struct Tilemap<T>
+{
+	pub width: u32,
+	pub height: u32,
+	tiles: Vec<T>,
+}
+
+impl<T> Tilemap<T>
+{
+	/// Returns `None` for cells outside the tilemap.
+	pub fn get(&self, cell: Cell) -> Option<&T>
+	{
+		if cell.x < 0 || cell.y < 0 || cell.x as u32 >= self.width || cell.y as u32 >= self.height
+			{ return None }
+		self.tiles.get(cell.y as usize * self.width as usize + cell.x as usize)
+	}
+
+	/// Every tile with its cell, row by row from the top-left.
+	pub fn iter(&self) -> impl Iterator<Item = (Cell, &T)>
+	{
+		let width = self.width as i32;
+		self.tiles.iter().enumerate().map(move |(index, tile)| (Cell::new(index as i32 % width, index as i32 / width), tile))
+	}
+}
+
+#[derive(Clone, Debug)]
+enum Step
+{
+	Noise { scale: f32, octaves: u32, amplitude: f32 },
+	RandomFill(f32),
+	Threshold(f32),
+	Smooth(u32),
+	Normalize,
+}
+
+/// Builds a `Heightmap` from a seed by running a list of steps in order. The same seed and steps always produce
+/// the same result, and each step gets its own random numbers, so adding a step never changes the steps before it.
+///
+/// For example, `Pipeline::new(80, 50).random_fill(0.45).smooth(5)` makes a roguelike cave, with `1.0` for walls.
+#[derive(Clone, Debug)]
+pub struct Pipeline
+{
+	width: u32,
+	height: u32,
+	steps: Vec<Step>,
+}
+
+impl Pipeline
+{
+	pub fn new(width: u32, height: u32) -> Pipeline
+	{
+		Pipeline {
+			width: width,
+			height: height,
+			steps: Vec::new(),
+		}
+	}
+
+	/// Adds smooth fractal value noise from `0.0` to `amplitude`. `scale` is the size of the largest features in
+	/// cells, and each extra octave adds detail at half the size and half the strength.
+	pub fn noise(mut self, scale: f32, octaves: u32, amplitude: f32) -> Pipeline
+	{
+		self.steps.push(Step::Noise { scale: scale, octaves: octaves.max(1), amplitude: amplitude });
+		self
+	}
+
+	/// Sets each cell to `1.0` with the given probability, and to `0.0` otherwise.
+	pub fn random_fill(mut self, probability: f32) -> Pipeline
+	{
+		self.steps.push(Step::RandomFill(probability));
+		self
+	}
+
+	/// Sets cells at or above `level` to `1.0`, and the rest to `0.0`.
+	pub fn threshold(mut self, level: f32) -> Pipeline
+	{
+		self.steps.push(Step::Threshold(level));
+		self
+	}
+
+	/// Runs a cellular automaton over a map of `1.0`s and `0.0`s: a cell becomes `1.0` if at least 5 of the 9 cells
+	/// around and including it are `1.0`. Cells outside the map count as `1.0`, so caves are closed at the edges.
+	/// This turns random noise into smooth, connected caves.
+	pub fn smooth(mut self, iterations: u32) -> Pipeline
+	{
+		self.steps.push(Step::Smooth(iterations));
+		self
+	}
+
+	/// Rescales the values so the lowest becomes `0.0` and the highest `1.0`.
+	pub fn normalize(mut self) -> Pipeline
+	{
+		self.steps.push(Step::Normalize);
+		self
+	}
+
+	pub fn generate(&self, seed: u64) -> Heightmap
+	{
+		let mut heightmap = Heightmap::new(self.width, self.height);
+		for (step_index, step) in self.steps.iter().enumerate()
+		{
+			let step_seed = Rng::new(seed ^ (step_index as u64).wrapping_mul(0xD6E8FEB86659FD93)).next_u64();
+			match step
+			{
+				Step::Noise { scale, octaves, amplitude } => add_noise(&mut heightmap, step_seed, *scale, *octaves, *amplitude),
+				Step::RandomFill(probability) => {
+					let mut rng = Rng::new(step_seed);
+					heightmap.values.iter_mut().for_each(|value| *value = if rng.chance(*probability) { 1.0 } else { 0.0 });
+				},
+				Step::Threshold(level) => {
+					heightmap.values.iter_mut().for_each(|value| *value = if *value >= *level { 1.0 } else { 0.0 });
+				},
+				Step::Smooth(iterations) => {
+					for _ in 0..*iterations
+					{
+						smooth(&mut heightmap);
+					}
+				},
+				Step::Normalize => normalize(&mut heightmap),
+			}
+		}
+		heightmap
+	}
+
+	/// Generates the heightmap and converts it into tiles with `biomes`.
+	pub fn generate_tiles<T: Clone>(&self, seed: u64, biomes: &Biomes<T>) -> Tilemap<T>
+	{
+		self.generate(seed).to_tilemap(biomes)
+	}
+}
+
+fn add_noise(heightmap: &mut Heightmap, seed: u64, scale: f32, octaves: u32, amplitude: f32)
+{
+	let scale = scale.max(f32::EPSILON);
+	// The octaves' strengths add up to almost 2, so dividing by their sum keeps the noise within 0..1.
+	let total_strength: f32 = (0..octaves).map(|octave| 0.5f32.powi(octave as i32)).sum();
+	for cell in heightmap.cells().collect::<Vec<Cell>>()
+	{
+		let mut noise = 0.0;
+		for octave in 0..octaves
+		{
+			let frequency = 2.0f32.powi(octave as i32) / scale;
+			let octave_seed = seed.wrapping_add(octave as u64);
+			noise += value_noise(cell.x as f32 * frequency, cell.y as f32 * frequency, octave_seed) * 0.5f32.powi(octave as i32);
+		}
+		let index = heightmap.index(cell);
+		heightmap.values[index] += noise / total_strength * amplitude;
+	}
+}
+
+fn value_noise(x: f32, y: f32, seed: u64) -> f32
+{
+	let (cell_x, cell_y) = (x.floor(), y.floor());
+	let (fraction_x, fraction_y) = (smoothstep(x - cell_x), smoothstep(y - cell_y));
+	let (cell_x, cell_y) = (cell_x as i64, cell_y as i64);
+
+	let top = lattice_value(cell_x, cell_y, seed).lerp(&lattice_value(cell_x + 1, cell_y, seed), fraction_x);
+	let bottom = lattice_value(cell_x, cell_y + 1, seed).lerp(&lattice_value(cell_x + 1, cell_y + 1, seed), fraction_x);
+	top.lerp(&bottom, fraction_y)
+}
+
+fn lattice_value(x: i64, y: i64, seed: u64) -> f32
+{
+	let hash = seed ^ (x as u64).wrapping_mul(0x9E3779B97F4A7C15) ^ (y as u64).wrapping_mul(0xC2B2AE3D27D4EB4F);
+	Rng::new(hash).next_f32()
+}
+
+fn smoothstep(t: f32) -> f32
+{
+	t * t * (3.0 - 2.0 * t)
+}
+
+fn smooth(heightmap: &mut Heightmap)
+{
+	let previous = heightmap.clone();
+	for cell in previous.cells()
+	{
+		let filled_neighbours = cell.neighbours(Neighbourhood::Eight)
+			.filter(|neighbour| previous.get(*neighbour).unwrap_or(1.0) >= 0.5)
+			.count();
+		let filled_self = (previous.get(cell).unwrap_or(0.0) >= 0.5) as usize;
+		heightmap.set(cell, if filled_neighbours + filled_self >= 5 { 1.0 } else { 0.0 });
+	}
+}
+
+fn normalize(heightmap: &mut Heightmap)
+{
+	let lowest = heightmap.values.iter().cloned().fold(f32::INFINITY, f32::min);
+	let highest = heightmap.values.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+	let range = highest - lowest;
+	heightmap.values.iter_mut().for_each(|value| *value = if range > 0.0 { (*value - lowest) / range } else { 0.0 });
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	#[test]
+	fn parses_dice_notation()
+	{
+		assert_eq!(Dice::parse("2d6+3").unwrap(), Dice::new(2, 6, 3));
+		assert_eq!(Dice::parse("d20").unwrap(), Dice::new(1, 20, 0));
+		assert_eq!(Dice::parse("3d8").unwrap(), Dice::new(3, 8, 0));
+		assert_eq!(Dice::parse("1d4-1").unwrap(), Dice::new(1, 4, -1));
+		assert_eq!(Dice::parse(" 2D6 + 3 ").unwrap(), Dice::new(2, 6, 3));
+	}
+
+	#[test]
+	fn rejects_invalid_dice_notation()
+	{
+		for notation in ["", "d", "2d", "6", "x", "0d6", "2d0", "2d6+", "2d6+x", "-2d6", "2d-6", "2d6+3+1", "99999999999d6"]
+		{
+			assert!(Dice::parse(notation).is_err(), "{:?} should not parse", notation);
+		}
+	}
+
+	#[test]
+	fn dice_min_and_max()
+	{
+		let dice = Dice::parse("2d6+3").unwrap();
+		assert_eq!((dice.min(), dice.max()), (5, 15));
+		let dice = Dice::parse("1d4-1").unwrap();
+		assert_eq!((dice.min(), dice.max()), (0, 3));
+	}
+
+	#[test]
+	fn dice_min_and_max_clamp_instead_of_overflowing()
+	{
+		assert_eq!(Dice::parse("10d1000000000").unwrap().max(), i32::MAX);
+		assert_eq!(Dice::parse("1d6+2147483647").unwrap().min(), i32::MAX);
+		assert_eq!(Dice::new(u32::MAX, 1, 0).min(), i32::MAX);
+		assert_eq!(Dice::new(1, 6, i32::MIN).min(), i32::MIN + 1);
+	}
+
+	#[test]
+	fn rolls_stay_within_min_and_max()
+	{
+		let dice = Dice::parse("3d6-2").unwrap();
+		let mut rng = Rng::new(1);
+		for _ in 0..1000
+		{
+			let roll = dice.roll(&mut rng);
+			assert!(roll >= dice.min() && roll <= dice.max());
+		}
+	}
+}
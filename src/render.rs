@@ -0,0 +1,106 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use std::mem::MaybeUninit;
+
+use sdl2::render::RendererInfo;
+use sdl2::sys::{self, SDL_RendererFlags};
+
+static LIVE_TEXTURES: AtomicUsize = AtomicUsize::new(0);
+static LIVE_TEXTURE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// What the renderer behind a `Window` supports, queried when the window is created.
+/// `vsync` is kept up to date by `window.set_vsync()`.
+pub struct Capabilities
+{
+	/// The SDL render driver, e.g. `"opengl"`, `"direct3d11"` or `"software"`.
+	pub renderer_name: String,
+	pub accelerated: bool,
+	pub vsync: bool,
+	pub render_to_texture: bool,
+	pub max_texture_width: u32,
+	pub max_texture_height: u32,
+}
+
+impl Capabilities
+{
+	pub fn from_sdl(renderer_info: &RendererInfo) -> Capabilities
+	{
+		let has_flag = |flag: SDL_RendererFlags| renderer_info.flags & flag as u32 != 0;
+		Capabilities {
+			renderer_name: renderer_info.name.to_string(),
+			accelerated: has_flag(SDL_RendererFlags::SDL_RENDERER_ACCELERATED),
+			vsync: has_flag(SDL_RendererFlags::SDL_RENDERER_PRESENTVSYNC),
+			render_to_texture: has_flag(SDL_RendererFlags::SDL_RENDERER_TARGETTEXTURE),
+			max_texture_width: renderer_info.max_texture_width,
+			max_texture_height: renderer_info.max_texture_height,
+		}
+	}
+
+	// For code that only has a `TextureCreator`, rather than the `Window` holding the capabilities.
+	pub(crate) fn query(renderer: *mut sys::SDL_Renderer) -> Option<Capabilities>
+	{
+		let mut renderer_info = MaybeUninit::uninit();
+		match unsafe { sys::SDL_GetRendererInfo(renderer, renderer_info.as_mut_ptr()) }
+		{
+			0 => Some(Capabilities::from_sdl(&unsafe { RendererInfo::from_ll(&renderer_info.assume_init()) })),
+			_ => None,
+		}
+	}
+
+	/// A `max_texture_width`/`max_texture_height` of `0` means the renderer reported no limit.
+	pub fn supports_texture_size(&self, width: u32, height: u32) -> bool
+	{
+		(self.max_texture_width == 0 || width <= self.max_texture_width)
+			&& (self.max_texture_height == 0 || height <= self.max_texture_height)
+	}
+}
+
+pub struct ResourceStats
+{
+	pub textures: usize,
+	/// Approximate, assuming 4 bytes per pixel.
+	pub texture_bytes: usize,
+}
+
+pub fn resource_stats() -> ResourceStats
+{
+	ResourceStats {
+		textures: LIVE_TEXTURES.load(Ordering::Relaxed),
+		texture_bytes: LIVE_TEXTURE_BYTES.load(Ordering::Relaxed),
+	}
+}
+
+/// Returns the number of bytes tracked, which must be passed back to `untrack_texture`.
+pub(crate) fn track_texture(width: u32, height: u32) -> usize
+{
+	let bytes = texture_bytes(width, height);
+	LIVE_TEXTURES.fetch_add(1, Ordering::Relaxed);
+	LIVE_TEXTURE_BYTES.fetch_add(bytes, Ordering::Relaxed);
+	bytes
+}
+
+pub(crate) fn untrack_texture(bytes: usize)
+{
+	saturating_sub(&LIVE_TEXTURES, 1);
+	saturating_sub(&LIVE_TEXTURE_BYTES, bytes);
+}
+
+// The counters must never wrap around, or a miscount would be reported as billions of leaked textures.
+fn saturating_sub(counter: &AtomicUsize, amount: usize)
+{
+	let _ = counter.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |value| Some(value.saturating_sub(amount)));
+}
+
+pub(crate) fn warn_leaked_resources()
+{
+	let stats = resource_stats();
+	if stats.textures > 0
+	{
+		eprintln!("Realms warning: {} texture(s) (~{} KiB) were still alive when the window was closed. Make sure every `Texture` is dropped when it is no longer needed.", stats.textures, stats.texture_bytes / 1024);
+	}
+}
+
+fn texture_bytes(width: u32, height: u32) -> usize
+{
+	width as usize * height as usize * 4
+}
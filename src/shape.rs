@@ -1,5 +1,7 @@
 use sdl2::rect::Rect as SdlRect;
 
+use crate::blend::{self, BlendMode};
+use crate::data;
 use crate::{window::Window, Colour, RealmsError};
 
 pub struct Rect
@@ -9,6 +11,7 @@ pub struct Rect
 	pub width: u32,
 	pub height: u32,
 	pub colour: Colour,
+	pub blend_mode: BlendMode,
 	pub sdl: SdlRect,
 }
 
@@ -22,12 +25,32 @@ impl Rect
 			width: width,
 			height: height,
 			colour: colour,
+			blend_mode: BlendMode::Alpha,
 			sdl: SdlRect::new(x, y, width, height),
 		}
 	}
 
+	pub fn bounds(&self) -> data::Rect
+	{
+		data::Rect::new(self.x as f32, self.y as f32, self.width as f32, self.height as f32)
+	}
+
+	/// Takes effect the next time the rect is drawn.
+	pub fn set_colour(&mut self, colour: Colour)
+	{
+		self.colour = colour;
+	}
+
+	/// Changes only the alpha of the rect's colour, for fading it in and out. `0` is invisible and `255` is opaque.
+	/// Has no effect with `BlendMode::None`.
+	pub fn set_alpha(&mut self, alpha: u8)
+	{
+		self.colour.a = alpha;
+	}
+
 	pub fn draw(&self, window: &mut Window) -> Result<(), RealmsError>
 	{
+		blend::set_draw_blend_mode(&window.sdl.canvas, &self.blend_mode)?;
 		window.sdl.canvas.set_draw_color(self.colour.to_sdl());
 		match window.sdl.canvas.fill_rect(self.sdl)
 		{
@@ -38,6 +61,7 @@ impl Rect
 
 	pub fn draw_outline(&self, window: &mut Window) -> Result<(), RealmsError>
 	{
+		blend::set_draw_blend_mode(&window.sdl.canvas, &self.blend_mode)?;
 		window.sdl.canvas.set_draw_color(self.colour.to_sdl());
 		match window.sdl.canvas.draw_rect(self.sdl)
 		{
@@ -47,3 +71,70 @@ impl Rect
 	}
 }
 
+
+struct StaticBatchRun
+{
+	colour: Colour,
+	blend_mode: BlendMode,
+	rects: Vec<SdlRect>,
+}
+
+/// Many rects merged into as few draw calls as possible, for level decoration that never changes.
+/// Consecutive rects with the same colour and blend mode are drawn together, so the original draw order is kept.
+pub struct StaticBatch
+{
+	runs: Vec<StaticBatchRun>,
+	alpha: u8,
+}
+
+impl StaticBatch
+{
+	pub fn bake(rects: &[&Rect]) -> StaticBatch
+	{
+		let mut runs: Vec<StaticBatchRun> = Vec::new();
+		for rect in rects
+		{
+			match runs.last_mut()
+			{
+				Some(run) if run.colour == rect.colour && run.blend_mode == rect.blend_mode => run.rects.push(rect.sdl),
+				_ => runs.push(StaticBatchRun {
+					colour: rect.colour.clone(),
+					blend_mode: rect.blend_mode.clone(),
+					rects: vec![rect.sdl],
+				}),
+			}
+		}
+
+		StaticBatch {
+			runs: runs,
+			alpha: 255,
+		}
+	}
+
+	/// Fades the whole batch without baking it again: each rect's alpha is multiplied by `alpha / 255`.
+	/// Has no effect on rects drawn with `BlendMode::None`.
+	pub fn set_alpha(&mut self, alpha: u8)
+	{
+		self.alpha = alpha;
+	}
+
+	pub fn get_alpha(&self) -> u8
+	{
+		self.alpha
+	}
+
+	pub fn draw(&self, window: &mut Window) -> Result<(), RealmsError>
+	{
+		for run in &self.runs
+		{
+			blend::set_draw_blend_mode(&window.sdl.canvas, &run.blend_mode)?;
+			let alpha = (run.colour.a as u32 * self.alpha as u32 / 255) as u8;
+			window.sdl.canvas.set_draw_color(Colour::from_rgba(run.colour.r, run.colour.g, run.colour.b, alpha).to_sdl());
+			if let Err(error_info) = window.sdl.canvas.fill_rects(&run.rects)
+			{
+				return Err(RealmsError::DrawRectError(error_info));
+			}
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,95 @@
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::anim::Lerp;
+
+struct Slots<T>
+{
+	previous: Option<(Arc<T>, Instant)>,
+	current: Option<(Arc<T>, Instant)>,
+}
+
+/// Publishes immutable snapshots of the game state, usually from a worker thread running the game logic.
+pub struct SnapshotSender<T>
+{
+	slots: Arc<Mutex<Slots<T>>>,
+}
+
+/// Reads the latest snapshots on the main thread, which owns the `Window` and does the drawing.
+pub struct SnapshotReceiver<T>
+{
+	slots: Arc<Mutex<Slots<T>>>,
+}
+
+/// The two most recent snapshots, and how far the renderer is between them.
+pub struct SnapshotPair<T>
+{
+	pub previous: Arc<T>,
+	pub current: Arc<T>,
+	/// `0.0` at `previous`, `1.0` at `current`.
+	pub alpha: f32,
+}
+
+/// Creates a double-buffered channel: only the two newest snapshots are kept, so a slow renderer never falls behind.
+pub fn snapshot_channel<T>() -> (SnapshotSender<T>, SnapshotReceiver<T>)
+{
+	let slots = Arc::new(Mutex::new(Slots {
+		previous: None,
+		current: None,
+	}));
+	(SnapshotSender { slots: slots.clone() }, SnapshotReceiver { slots: slots })
+}
+
+impl<T> SnapshotSender<T>
+{
+	pub fn publish(&self, snapshot: T)
+	{
+		let mut slots = self.slots.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+		slots.previous = slots.current.take();
+		slots.current = Some((Arc::new(snapshot), Instant::now()));
+	}
+}
+
+impl<T> SnapshotReceiver<T>
+{
+	pub fn latest(&self) -> Option<Arc<T>>
+	{
+		let slots = self.slots.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+		slots.current.as_ref().map(|(snapshot, _)| snapshot.clone())
+	}
+
+	/// Returns the two newest snapshots, with `alpha` based on how long ago the newest one was published
+	/// compared to the time between the two. Drawing the interpolated state keeps motion smooth
+	/// when the game logic updates less often than the screen refreshes, at the cost of one update of latency.
+	pub fn pair(&self) -> Option<SnapshotPair<T>>
+	{
+		let slots = self.slots.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+		let (current, current_time) = slots.current.as_ref()?;
+		let (previous, previous_time) = match &slots.previous
+		{
+			Some((previous, previous_time)) => (previous, previous_time),
+			None => (current, current_time),
+		};
+
+		let interval = current_time.duration_since(*previous_time).as_secs_f32();
+		let alpha = match interval > 0.0
+		{
+			true => (current_time.elapsed().as_secs_f32() / interval).clamp(0.0, 1.0),
+			false => 1.0,
+		};
+
+		Some(SnapshotPair {
+			previous: previous.clone(),
+			current: current.clone(),
+			alpha: alpha,
+		})
+	}
+}
+
+impl<T: Lerp> SnapshotPair<T>
+{
+	pub fn interpolate(&self) -> T
+	{
+		self.previous.lerp(&self.current, self.alpha)
+	}
+}
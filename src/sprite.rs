@@ -1,16 +1,29 @@
+use std::mem::{self, ManuallyDrop};
+use std::ptr;
+
 use sdl2::image::LoadTexture;
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::rect::Point as SdlPoint;
+use sdl2::render::Canvas;
 use sdl2::render::Texture as SdlTexture;
 use sdl2::render::TextureCreator as SdlTextureCreator;
+use sdl2::sys;
+use sdl2::video::Window as SdlWindow;
 use sdl2::video::WindowContext as SdlWindowContext;
 
+use crate::blend::{self, BlendMode};
+use crate::data;
+use crate::render;
 use crate::window::Window;
-use crate::RealmsError;
+use crate::{Colour, RealmsError};
 
 pub struct Texture<'a>
 {
 	pub sdl_texture: SdlTexture<'a>,
 	pub width: u32,
 	pub height: u32,
+	// What was added to `render::resource_stats()`, so it can be removed exactly on drop.
+	tracked_bytes: usize,
 }
 
 impl<'a> Texture<'a>
@@ -25,18 +38,105 @@ impl<'a> Texture<'a>
 					sdl_texture: sdl_texture,
 					width: image_info.width,
 					height: image_info.height,
+					tracked_bytes: render::track_texture(image_info.width, image_info.height),
 				}
 			}),
 			Err(error_info) => Err(RealmsError::LoadTextureError(error_info))
 		}
 	}
+
+	/// `pixels` are read row by row, starting from the top-left, and must contain `width * height` colours.
+	/// Fails if the texture is larger than the renderer's maximum texture size.
+	pub fn from_pixels(pixels: &[Colour], width: u32, height: u32, texture_creator: &'a SdlTextureCreator<SdlWindowContext>) -> Result<Texture<'a>, RealmsError>
+	{
+		let byte_count = match (width as usize).checked_mul(height as usize).and_then(|count| count.checked_mul(4))
+		{
+			Some(byte_count) => byte_count,
+			None => return Err(RealmsError::CreateTextureError(format!("a {}x{} texture is too large", width, height))),
+		};
+		if pixels.len() != byte_count / 4
+		{
+			return Err(RealmsError::CreateTextureError(format!("expected {} pixels for a {}x{} texture, got {}", byte_count / 4, width, height, pixels.len())));
+		}
+		if let Some(capabilities) = render::Capabilities::query(texture_creator.raw())
+		{
+			if !capabilities.supports_texture_size(width, height)
+			{
+				return Err(RealmsError::CreateTextureError(format!("a {}x{} texture is larger than the renderer's maximum texture size of {}x{}", width, height, capabilities.max_texture_width, capabilities.max_texture_height)));
+			}
+		}
+
+		let mut bytes: Vec<u8> = Vec::with_capacity(byte_count);
+		for colour in pixels
+		{
+			let argb = (colour.a as u32) << 24 | (colour.r as u32) << 16 | (colour.g as u32) << 8 | colour.b as u32;
+			bytes.extend_from_slice(&argb.to_ne_bytes());
+		}
+
+		match texture_creator.create_texture_static(PixelFormatEnum::ARGB8888, width, height)
+		{
+			Ok(mut sdl_texture) => match sdl_texture.update(None, &bytes, width as usize * 4)
+			{
+				Ok(()) => Ok(Texture {
+					sdl_texture: sdl_texture,
+					width: width,
+					height: height,
+					tracked_bytes: render::track_texture(width, height),
+				}),
+				Err(error_info) => Err(RealmsError::CreateTextureError(error_info.to_string()))
+			},
+			Err(error_info) => Err(RealmsError::CreateTextureError(error_info.to_string()))
+		}
+	}
+
+	// Wraps a texture created directly with SDL, so it is counted by `render::resource_stats()`.
+	pub(crate) fn from_sdl_texture(sdl_texture: SdlTexture<'a>) -> Texture<'a>
+	{
+		let image_info = sdl_texture.query();
+		Texture {
+			sdl_texture: sdl_texture,
+			width: image_info.width,
+			height: image_info.height,
+			tracked_bytes: render::track_texture(image_info.width, image_info.height),
+		}
+	}
+
+	/// Takes ownership of the underlying SDL texture, which is then no longer counted by `render::resource_stats()`.
+	/// Use this instead of destructuring, which `Texture` does not allow.
+	pub fn into_sdl_texture(self) -> SdlTexture<'a>
+	{
+		render::untrack_texture(self.tracked_bytes);
+		let texture = ManuallyDrop::new(self);
+		// `texture` is never dropped, so the SDL texture is moved out exactly once.
+		unsafe { ptr::read(&texture.sdl_texture) }
+	}
 }
 
+impl Drop for Texture<'_>
+{
+	fn drop(&mut self)
+	{
+		render::untrack_texture(self.tracked_bytes);
+	}
+}
+
+// The directions the silhouette is shifted in to build an outline around it.
+const OUTLINE_OFFSETS: [(i32, i32); 8] = [(0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)];
+
 pub struct Sprite<'a>
 {
 	pub texture: &'a Texture<'a>,
 	pub x: i32,
 	pub y: i32,
+	pub blend_mode: BlendMode,
+	pub flip_x: bool,
+	pub flip_y: bool,
+	/// Clockwise rotation in degrees.
+	pub angle: f64,
+	/// The point the sprite rotates around, relative to its top-left corner. `None` rotates around the centre.
+	pub origin: Option<(i32, i32)>,
+	/// Multiplied with the texture's colours. White leaves the texture unchanged.
+	pub tint: Colour,
 }
 
 impl<'a> Sprite<'a>
@@ -46,19 +146,76 @@ impl<'a> Sprite<'a>
 		Sprite {
 			texture: texture,
 			x: x,
-			y: y
+			y: y,
+			blend_mode: BlendMode::Alpha,
+			flip_x: false,
+			flip_y: false,
+			angle: 0.0,
+			origin: None,
+			tint: Colour::from_rgb(255, 255, 255),
 		}
 	}
 
+	/// The area the sprite covers when it is not rotated.
+	pub fn bounds(&self) -> data::Rect
+	{
+		data::Rect::new(self.x as f32, self.y as f32, self.texture.width as f32, self.texture.height as f32)
+	}
+
 	pub fn draw(&mut self, window: &mut Window) -> Result<(), RealmsError>
 	{
+		self.copy_to(&mut window.sdl.canvas, self.x, self.y)
+	}
+
+	// Draws the sprite with its top-left corner at `(x, y)` on `canvas`, which may be targeting a texture.
+	pub(crate) fn copy_to(&self, canvas: &mut Canvas<SdlWindow>, x: i32, y: i32) -> Result<(), RealmsError>
+	{
+		blend::set_texture_blend_mode(self.texture, &self.blend_mode)?;
+		self.apply_tint()?;
 		let src = sdl2::rect::Rect::new(0, 0, self.texture.width, self.texture.height);
-		let dest = sdl2::rect::Rect::new(self.x, self.y, self.texture.width, self.texture.height);
+		let dest = sdl2::rect::Rect::new(x, y, self.texture.width, self.texture.height);
+		let origin = self.origin.map(|(x, y)| SdlPoint::new(x, y));
 
-		match window.sdl.canvas.copy(&self.texture.sdl_texture, src, dest)
+		match canvas.copy_ex(&self.texture.sdl_texture, src, dest, self.angle, origin, self.flip_x, self.flip_y)
 		{
 			Ok(()) => Ok(()),
 			Err(error_info) => Err(RealmsError::DrawSpriteError(error_info))
 		}
 	}
+
+	/// Draws the sprite with an outline `thickness` pixels wide that follows the shape of its visible pixels,
+	/// e.g. to show it is selected. The outline follows rotation and flipping.
+	///
+	/// The outline is the sprite drawn with `colour` as its tint, so it is exactly `colour` where the texture is
+	/// white and darker where the texture is darker. Textures with light edges give the most even outline.
+	pub fn draw_outlined(&mut self, window: &mut Window, colour: Colour, thickness: u32) -> Result<(), RealmsError>
+	{
+		let tint = mem::replace(&mut self.tint, colour);
+		let mut result = Ok(());
+		'outline: for distance in 1..=thickness as i32
+		{
+			for (offset_x, offset_y) in OUTLINE_OFFSETS
+			{
+				result = self.copy_to(&mut window.sdl.canvas, self.x + offset_x * distance, self.y + offset_y * distance);
+				if result.is_err()
+					{ break 'outline }
+			}
+		}
+		self.tint = tint;
+		result?;
+		self.draw(window)
+	}
+
+	// The colour and alpha mods belong to the texture, so they are set on every draw for sprites sharing it.
+	fn apply_tint(&self) -> Result<(), RealmsError>
+	{
+		let raw_texture = self.texture.sdl_texture.raw();
+		let colour_result = unsafe { sys::SDL_SetTextureColorMod(raw_texture, self.tint.r, self.tint.g, self.tint.b) };
+		let alpha_result = unsafe { sys::SDL_SetTextureAlphaMod(raw_texture, self.tint.a) };
+		match (colour_result, alpha_result)
+		{
+			(0, 0) => Ok(()),
+			_ => Err(RealmsError::DrawSpriteError(sdl2::get_error())),
+		}
+	}
 }
@@ -0,0 +1,90 @@
+use std::hash::{Hash, Hasher};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// An FNV-1a hasher. Unlike `DefaultHasher`, its output does not depend on the Rust version,
+/// so hashes can be stored and compared across builds. Note that `usize`/`isize` still hash
+/// differently on 32-bit and 64-bit platforms, so prefer fixed-size integers in game state.
+pub struct StableHasher
+{
+	hash: u64,
+}
+
+impl StableHasher
+{
+	pub fn new() -> StableHasher
+	{
+		StableHasher {
+			hash: FNV_OFFSET_BASIS,
+		}
+	}
+}
+
+impl Default for StableHasher
+{
+	fn default() -> StableHasher
+	{
+		StableHasher::new()
+	}
+}
+
+impl Hasher for StableHasher
+{
+	fn write(&mut self, bytes: &[u8])
+	{
+		for byte in bytes
+		{
+			self.hash ^= *byte as u64;
+			self.hash = self.hash.wrapping_mul(FNV_PRIME);
+		}
+	}
+
+	fn finish(&self) -> u64
+	{
+		self.hash
+	}
+}
+
+pub fn hash_state<S: Hash>(state: &S) -> u64
+{
+	let mut hasher = StableHasher::new();
+	state.hash(&mut hasher);
+	hasher.finish()
+}
+
+/// Runs `update` once per recorded input, returning the hash of the game state after each frame.
+pub fn record_hashes<S: Hash, I>(state: &mut S, inputs: &[I], mut update: impl FnMut(&mut S, &I)) -> Vec<u64>
+{
+	let mut hashes: Vec<u64> = Vec::with_capacity(inputs.len());
+	for input in inputs
+	{
+		update(state, input);
+		hashes.push(hash_state(state));
+	}
+	hashes
+}
+
+/// Returns the first frame at which two runs diverged, or `None` if they match.
+pub fn first_mismatch(expected: &[u64], actual: &[u64]) -> Option<usize>
+{
+	match expected.iter().zip(actual).position(|(expected, actual)| expected != actual)
+	{
+		Some(frame) => Some(frame),
+		None if expected.len() != actual.len() => Some(expected.len().min(actual.len())),
+		None => None,
+	}
+}
+
+/// Runs the same inputs twice from fresh states and checks every frame hashes identically.
+/// On failure, returns the first frame that differed.
+pub fn check_determinism<S: Hash, I>(mut new_state: impl FnMut() -> S, inputs: &[I], mut update: impl FnMut(&mut S, &I)) -> Result<(), usize>
+{
+	let first_run = record_hashes(&mut new_state(), inputs, &mut update);
+	let second_run = record_hashes(&mut new_state(), inputs, &mut update);
+	match first_mismatch(&first_run, &second_run)
+	{
+		Some(frame) => Err(frame),
+		None => Ok(()),
+	}
+}
@@ -0,0 +1,127 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Sleeps between frames so that they are submitted at a steady rate.
+/// Each deadline is scheduled from the previous deadline rather than from when the frame actually finished,
+/// so small amounts of jitter do not accumulate. If a frame runs late, the schedule is restarted from now.
+pub struct FramePacer
+{
+	pub frame_duration: Duration,
+	next_frame: Instant,
+}
+
+impl FramePacer
+{
+	pub fn new(frames_per_second: u32) -> FramePacer
+	{
+		let frame_duration = Duration::from_secs(1) / frames_per_second.max(1);
+		FramePacer {
+			frame_duration: frame_duration,
+			next_frame: Instant::now() + frame_duration,
+		}
+	}
+
+	pub fn wait(&mut self)
+	{
+		let now = Instant::now();
+		if now < self.next_frame
+		{
+			thread::sleep(self.next_frame - now);
+			self.next_frame += self.frame_duration;
+		}
+		else
+		{
+			self.next_frame = now + self.frame_duration;
+		}
+	}
+}
+
+/// Scales, pauses or single-steps game time. Pass each frame's delta time through `apply`
+/// and use the result to update gameplay.
+pub struct TimeScale
+{
+	/// `1.0` is normal speed, `0.1` is ten times slower.
+	pub scale: f64,
+	paused: bool,
+	step_pending: bool,
+}
+
+impl TimeScale
+{
+	pub fn new() -> TimeScale
+	{
+		TimeScale {
+			scale: 1.0,
+			paused: false,
+			step_pending: false,
+		}
+	}
+
+	pub fn pause(&mut self)
+	{
+		self.paused = true;
+	}
+
+	pub fn resume(&mut self)
+	{
+		self.paused = false;
+	}
+
+	pub fn toggle_pause(&mut self)
+	{
+		self.paused = !self.paused;
+	}
+
+	pub fn is_paused(&self) -> bool
+	{
+		self.paused
+	}
+
+	/// While paused, lets exactly one more frame of time through on the next call to `apply`.
+	pub fn step(&mut self)
+	{
+		self.step_pending = true;
+	}
+
+	pub fn apply(&mut self, delta: Duration) -> Duration
+	{
+		if self.paused && !self.step_pending
+			{ return Duration::ZERO }
+		self.step_pending = false;
+		delta.mul_f64(self.scale.max(0.0))
+	}
+}
+
+impl Default for TimeScale
+{
+	fn default() -> TimeScale
+	{
+		TimeScale::new()
+	}
+}
+
+/// Timing for one frame, returned by `window.new_frame()`. Multiply speeds by `delta_seconds()` so that movement
+/// is the same at any framerate.
+#[derive(Clone, Copy, Debug)]
+pub struct GameTime
+{
+	/// Time since the previous call to `window.new_frame()`, or zero on the first call.
+	pub delta: Duration,
+	/// Time since the window was created.
+	pub elapsed: Duration,
+	/// The number of frames drawn with `window.draw()` so far.
+	pub frame: u64,
+}
+
+impl GameTime
+{
+	pub fn delta_seconds(&self) -> f32
+	{
+		self.delta.as_secs_f32()
+	}
+
+	pub fn elapsed_seconds(&self) -> f64
+	{
+		self.elapsed.as_secs_f64()
+	}
+}
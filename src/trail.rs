@@ -0,0 +1,263 @@
+use std::collections::VecDeque;
+use std::ptr;
+use std::time::Duration;
+
+use sdl2::sys::{self, SDL_Color, SDL_FPoint, SDL_Vertex};
+
+use crate::blend::{self, BlendMode};
+use crate::math::Vec2;
+use crate::sprite::Texture;
+use crate::window::Window;
+use crate::{Colour, RealmsError};
+
+struct TrailPoint
+{
+	position: Vec2,
+	age: Duration,
+}
+
+// A point along the ribbon, ready to be turned into vertices.
+struct RibbonSample
+{
+	position: Vec2,
+	/// Half the width, pointing from the centre line to one edge.
+	offset: Vec2,
+	alpha: u8,
+	/// Distance along the texture, in texture lengths. Not wrapped into `0..1`.
+	u: f32,
+}
+
+/// Records positions over time (for example a sword tip or a projectile) and draws a ribbon through them,
+/// tapering from `width` at the newest point to `end_width` at the oldest.
+///
+/// Call `add_point()` and `update()` every frame, then `draw()`.
+pub struct TrailRenderer<'a>
+{
+	/// Stretched or repeated along the ribbon, with the top of the texture on one edge and the bottom on the other.
+	/// `None` draws the ribbon in a solid colour.
+	pub texture: Option<&'a Texture<'a>>,
+	/// Multiplied with the texture, or the ribbon's colour if there is no texture.
+	pub colour: Colour,
+	pub blend_mode: BlendMode,
+	pub width: f32,
+	pub end_width: f32,
+	/// The most points kept. The oldest are removed first.
+	pub max_points: usize,
+	/// Points are removed once they are this old, fading out as they age. Zero keeps points until `max_points` is reached.
+	pub fade_time: Duration,
+	/// A new point is only recorded once the head has moved this far, which keeps the ribbon smooth when moving slowly.
+	pub min_distance: f32,
+	/// The distance covered by one repeat of the texture. Zero stretches the texture once over the whole ribbon.
+	pub texture_length: f32,
+	/// How many texture lengths the texture scrolls along the ribbon per second, towards the tail when positive.
+	pub scroll_speed: f32,
+	points: VecDeque<TrailPoint>,
+	scroll_offset: f32,
+}
+
+impl<'a> TrailRenderer<'a>
+{
+	pub fn new(width: f32, colour: Colour) -> TrailRenderer<'a>
+	{
+		TrailRenderer {
+			texture: None,
+			colour: colour,
+			blend_mode: BlendMode::Alpha,
+			width: width,
+			end_width: 0.0,
+			max_points: 32,
+			fade_time: Duration::from_millis(500),
+			min_distance: 4.0,
+			texture_length: 0.0,
+			scroll_speed: 0.0,
+			points: VecDeque::new(),
+			scroll_offset: 0.0,
+		}
+	}
+
+	/// Moves the head of the trail to `position`, recording a new point if it has moved at least `min_distance`.
+	pub fn add_point(&mut self, position: Vec2)
+	{
+		let moved_far_enough = match self.points.front()
+		{
+			Some(head) => head.position.distance_to(position) >= self.min_distance,
+			None => true,
+		};
+		// The head always follows the position, so the ribbon stays attached between recorded points.
+		match (moved_far_enough, self.points.front_mut())
+		{
+			(false, Some(head)) => {
+				head.position = position;
+				head.age = Duration::ZERO;
+			},
+			_ => self.points.push_front(TrailPoint {
+				position: position,
+				age: Duration::ZERO,
+			}),
+		}
+		self.points.truncate(self.max_points.max(2));
+	}
+
+	/// Ages the points, removing any older than `fade_time`, and scrolls the texture.
+	pub fn update(&mut self, delta: Duration)
+	{
+		for point in self.points.iter_mut()
+		{
+			point.age += delta;
+		}
+		if !self.fade_time.is_zero()
+		{
+			self.points.retain(|point| point.age < self.fade_time);
+		}
+		self.scroll_offset = (self.scroll_offset - self.scroll_speed * delta.as_secs_f32()).rem_euclid(1.0);
+	}
+
+	pub fn clear(&mut self)
+	{
+		self.points.clear();
+	}
+
+	pub fn len(&self) -> usize
+	{
+		self.points.len()
+	}
+
+	pub fn is_empty(&self) -> bool
+	{
+		self.points.is_empty()
+	}
+
+	pub fn draw(&self, window: &mut Window) -> Result<(), RealmsError>
+	{
+		let samples = self.samples();
+		if samples.len() < 2
+			{ return Ok(()) }
+
+		let mut mesh = RibbonMesh::default();
+		for pair in samples.windows(2)
+		{
+			mesh.add_segment(&pair[0], &pair[1], &self.colour);
+		}
+
+		let raw_texture = match self.texture
+		{
+			Some(texture) => {
+				blend::set_texture_blend_mode(texture, &self.blend_mode)?;
+				texture.sdl_texture.raw()
+			},
+			None => {
+				blend::set_draw_blend_mode(&window.sdl.canvas, &self.blend_mode)?;
+				ptr::null_mut()
+			},
+		};
+		let result = unsafe {
+			sys::SDL_RenderGeometry(
+				window.sdl.canvas.raw(), raw_texture,
+				mesh.vertices.as_ptr(), mesh.vertices.len() as i32,
+				mesh.indices.as_ptr(), mesh.indices.len() as i32
+			)
+		};
+		match result
+		{
+			0 => Ok(()),
+			_ => Err(RealmsError::DrawTrailError(sdl2::get_error())),
+		}
+	}
+
+	fn samples(&self) -> Vec<RibbonSample>
+	{
+		let positions: Vec<Vec2> = self.points.iter().map(|point| point.position).collect();
+		let last = positions.len().saturating_sub(1);
+		let total_length: f32 = positions.windows(2).map(|pair| pair[0].distance_to(pair[1])).sum();
+
+		let mut samples: Vec<RibbonSample> = Vec::with_capacity(positions.len());
+		let mut distance = 0.0;
+		for (index, point) in self.points.iter().enumerate()
+		{
+			if index > 0
+			{
+				distance += positions[index - 1].distance_to(point.position);
+			}
+			// Each point's edges are perpendicular to the line through its neighbours, so the ribbon bends smoothly.
+			let direction = (positions[index.saturating_sub(1)] - positions[(index + 1).min(last)]).normalized();
+			let along = match total_length > 0.0
+			{
+				true => distance / total_length,
+				false => 0.0,
+			};
+			let half_width = (self.width + (self.end_width - self.width) * along) / 2.0;
+			let fade = match self.fade_time.is_zero()
+			{
+				true => 1.0,
+				false => 1.0 - (point.age.as_secs_f32() / self.fade_time.as_secs_f32()).min(1.0),
+			};
+			let u = match self.texture_length > 0.0
+			{
+				true => distance / self.texture_length,
+				false => along,
+			};
+			samples.push(RibbonSample {
+				position: point.position,
+				offset: Vec2::new(-direction.y, direction.x) * half_width,
+				alpha: (self.colour.a as f32 * fade).round() as u8,
+				u: u + self.scroll_offset,
+			});
+		}
+		samples
+	}
+}
+
+#[derive(Default)]
+struct RibbonMesh
+{
+	vertices: Vec<SDL_Vertex>,
+	indices: Vec<i32>,
+}
+
+impl RibbonMesh
+{
+	// SDL clamps texture coordinates rather than repeating the texture, so a segment that crosses the end of one
+	// repeat is split there and the next part starts again from the beginning of the texture.
+	fn add_segment(&mut self, start: &RibbonSample, end: &RibbonSample, colour: &Colour)
+	{
+		let mut from = 0.0;
+		let mut repeat = start.u.floor();
+		loop
+		{
+			let to = match end.u > repeat + 1.0
+			{
+				true => ((repeat + 1.0 - start.u) / (end.u - start.u)).clamp(from, 1.0),
+				false => 1.0,
+			};
+			self.add_quad(start, end, (from, to), repeat, colour);
+			if to >= 1.0
+				{ break }
+			from = to;
+			repeat += 1.0;
+		}
+	}
+
+	fn add_quad(&mut self, start: &RibbonSample, end: &RibbonSample, (from, to): (f32, f32), repeat: f32, colour: &Colour)
+	{
+		let first = self.vertices.len() as i32;
+		for t in [from, to]
+		{
+			let position = start.position + (end.position - start.position) * t;
+			let offset = start.offset + (end.offset - start.offset) * t;
+			let alpha = (start.alpha as f32 + (end.alpha as f32 - start.alpha as f32) * t).round() as u8;
+			let u = start.u + (end.u - start.u) * t - repeat;
+			self.vertices.push(vertex(position + offset, colour, alpha, u, 0.0));
+			self.vertices.push(vertex(position - offset, colour, alpha, u, 1.0));
+		}
+		self.indices.extend_from_slice(&[first, first + 1, first + 2, first + 1, first + 3, first + 2]);
+	}
+}
+
+fn vertex(position: Vec2, colour: &Colour, alpha: u8, u: f32, v: f32) -> SDL_Vertex
+{
+	SDL_Vertex {
+		position: SDL_FPoint { x: position.x, y: position.y },
+		color: SDL_Color { r: colour.r, g: colour.g, b: colour.b, a: alpha },
+		tex_coord: SDL_FPoint { x: u, y: v },
+	}
+}
@@ -1,9 +1,17 @@
+use sdl2::event::{Event as SdlEvent, WindowEvent as SdlWindowEvent};
 use sdl2::rect::Rect as SdlRect;
 use sdl2::render::Canvas;
-use sdl2::{EventPump, Sdl, VideoSubsystem};
-use sdl2::video::Window as SdlWindow;
+use sdl2::sys::{self, SDL_bool, SDL_FlashOperation, SDL_WindowFlags};
+use sdl2::controller::GameController;
+use sdl2::{EventPump, GameControllerSubsystem, Sdl, VideoSubsystem};
+use sdl2::video::{DisplayMode as SdlDisplayMode, FullscreenType, Window as SdlWindow, WindowPos};
+use std::time::{Duration, Instant};
 
-use crate::event::Event;
+use crate::blend::{self, BlendMode};
+use crate::display::{DisplayMode, Monitor, VideoMode};
+use crate::event::{ContentScaleEvent, Event, TimedEvent};
+use crate::render::{self, Capabilities};
+use crate::time::{FramePacer, GameTime};
 use crate::Colour;
 use crate::RealmsError;
 
@@ -14,6 +22,18 @@ pub struct SdlDisplay
 	// pub window: SdlWindow,
 	pub canvas: Canvas<SdlWindow>,
 	pub event_pump: EventPump,
+	pub game_controller: GameControllerSubsystem,
+}
+
+/// How the drawing area responds when the window is resized.
+pub enum ResizePolicy
+{
+	/// The drawing area grows and shrinks with the window. `window.width` and `window.height` follow the window size.
+	Free,
+	/// The original drawing area is stretched to fill the window, ignoring its aspect ratio.
+	Stretch,
+	/// The original drawing area is scaled to fit the window, with black bars to keep its aspect ratio.
+	Letterbox,
 }
 
 struct DefaultSurfaces
@@ -23,79 +43,524 @@ struct DefaultSurfaces
 
 pub struct Window<'a>
 {
+	/// The title the window was created with. Use `get_title()` for the current title.
 	pub title: &'a str,
 	pub width: u32,
 	pub height: u32,
 	pub sdl: SdlDisplay,
 	default_surfaces: DefaultSurfaces,
 	did_fill: bool,
+	no_clear: bool,
+	controllers: Vec<GameController>,
+	frame_pacer: Option<FramePacer>,
+	resize_policy: ResizePolicy,
+	aspect_ratio: Option<(u32, u32)>,
+	created_at: Instant,
+	frame_count: u64,
+	last_frame: Option<Instant>,
+	display_mode: DisplayMode,
+	windowed_size: (u32, u32),
+	brightness: f32,
+	capabilities: Capabilities,
+	content_scale: (f32, f32),
+	msaa_samples: u8,
 }
 
-impl Window<'_>
+/// Configures a `Window` before it is opened. Create one with `Window::builder()`.
+pub struct WindowBuilder<'a>
+{
+	title: &'a str,
+	width: u32,
+	height: u32,
+	position: Option<(i32, i32)>,
+	resizable: bool,
+	borderless: bool,
+	maximized: bool,
+	vsync: bool,
+	high_dpi: bool,
+	msaa_samples: u8,
+	display_mode: DisplayMode,
+}
+
+impl<'a> WindowBuilder<'a>
 {
+	pub fn new() -> WindowBuilder<'a>
+	{
+		WindowBuilder {
+			title: "Realms",
+			width: 800,
+			height: 600,
+			position: None,
+			resizable: false,
+			borderless: false,
+			maximized: false,
+			vsync: false,
+			high_dpi: false,
+			msaa_samples: 0,
+			display_mode: DisplayMode::Windowed,
+		}
+	}
+
+	pub fn title(mut self, title: &'a str) -> WindowBuilder<'a>
+	{
+		self.title = title;
+		self
+	}
+
+	pub fn size(mut self, width: u32, height: u32) -> WindowBuilder<'a>
+	{
+		self.width = width;
+		self.height = height;
+		self
+	}
+
+	/// Places the top-left corner of the window at (`x`, `y`) on the desktop. By default the platform chooses.
+	pub fn position(mut self, x: i32, y: i32) -> WindowBuilder<'a>
+	{
+		self.position = Some((x, y));
+		self
+	}
+
+	pub fn resizable(mut self, resizable: bool) -> WindowBuilder<'a>
+	{
+		self.resizable = resizable;
+		self
+	}
+
+	/// Removes the title bar and window border.
+	pub fn borderless(mut self, borderless: bool) -> WindowBuilder<'a>
+	{
+		self.borderless = borderless;
+		self
+	}
+
+	pub fn maximized(mut self, maximized: bool) -> WindowBuilder<'a>
+	{
+		self.maximized = maximized;
+		self
+	}
+
+	/// Makes `window.draw()` wait for the monitor to refresh before presenting, preventing tearing.
+	pub fn vsync(mut self, vsync: bool) -> WindowBuilder<'a>
+	{
+		self.vsync = vsync;
+		self
+	}
+
+	/// Renders at the full resolution of Hi-DPI displays (e.g. retina) instead of being upscaled and blurry.
+	/// Drawing coordinates stay the same, see `window.content_scale()`.
+	pub fn high_dpi(mut self, high_dpi: bool) -> WindowBuilder<'a>
+	{
+		self.high_dpi = high_dpi;
+		self
+	}
+
+	/// Requests multisample anti-aliasing with this many samples per pixel (usually 2, 4 or 8), which smooths the
+	/// edges of rotated sprites. `0` disables it. This switches this window's renderer to OpenGL, without affecting
+	/// other windows; check `window.get_msaa_samples()` for how many samples the driver actually granted.
+	pub fn msaa(mut self, samples: u8) -> WindowBuilder<'a>
+	{
+		self.msaa_samples = samples;
+		self
+	}
+
+	pub fn display_mode(mut self, display_mode: DisplayMode) -> WindowBuilder<'a>
+	{
+		self.display_mode = display_mode;
+		self
+	}
+
 	/// You must store the Window object as a **mutable** object, or SDL functions will fail.
-	/// 
-	/// > (error handling needs testing)
-	pub fn new(title: &str, width: u32, height: u32) -> Result<Window, RealmsError>
+	pub fn build(self) -> Result<Window<'a>, RealmsError>
 	{
 		match sdl2::init()
 		{
 			Ok(sdl2_context) => match sdl2_context.video()
 			{
-				Ok(sdl_video_subsystem) => match sdl_video_subsystem.window(&title, width.clone(), height.clone()).build()
+				Ok(sdl_video_subsystem) =>
 				{
-					Ok(sdl_window) => match sdl_window.into_canvas().build()
+					let sdl_window = self.build_sdl_window(&sdl_video_subsystem)?;
+					let sdl_canvas = self.build_sdl_canvas(sdl_window)?;
+					let sdl_controller_subsystem = sdl2_context.game_controller().map_err(RealmsError::CreateSdlControllerSubsystemError)?;
+					match sdl2_context.event_pump()
 					{
-						Ok(sdl_canvas) => match sdl2_context.event_pump()
+						Ok(event_pump) =>
 						{
-							Ok(event_pump) =>
+							let capabilities = Capabilities::from_sdl(&sdl_canvas.info());
+							let msaa_samples = self.enable_msaa(&sdl_video_subsystem, &sdl_canvas);
+							if self.msaa_samples > 0
+							{
+								// The GL attributes apply to every context created afterwards, so they are reset for other windows.
+								sdl_video_subsystem.gl_attr().set_multisample_buffers(0);
+								sdl_video_subsystem.gl_attr().set_multisample_samples(0);
+							}
+							let sdl_display = SdlDisplay {
+								context: sdl2_context,
+								video: sdl_video_subsystem,
+								// window: sdl_window,
+								canvas: sdl_canvas,
+								event_pump: event_pump,
+								game_controller: sdl_controller_subsystem,
+							};
+
+							let mut window = Window {
+								title: self.title,
+								width: self.width,
+								height: self.height,
+								sdl: sdl_display,
+								default_surfaces: Window::get_default_surfaces(self.width, self.height),
+								did_fill: true,
+								no_clear: false,
+								controllers: Vec::new(),
+								frame_pacer: None,
+								resize_policy: ResizePolicy::Free,
+								aspect_ratio: None,
+								created_at: Instant::now(),
+								frame_count: 0,
+								last_frame: None,
+								display_mode: DisplayMode::Windowed,
+								windowed_size: (self.width, self.height),
+								brightness: 1.0,
+								capabilities: capabilities,
+								content_scale: (1.0, 1.0),
+								msaa_samples: msaa_samples,
+							};
+							window.content_scale = window.query_content_scale();
+							let _ = window.update_scale();
+							if self.display_mode != DisplayMode::Windowed
 							{
-								let sdl_display = SdlDisplay {
-									context: sdl2_context,
-									video: sdl_video_subsystem,
-									// window: sdl_window,
-									canvas: sdl_canvas,
-									event_pump: event_pump,
-								};
-
-								Ok(Window {
-									title: title,
-									width: width,
-									height: height,
-									sdl: sdl_display,
-									default_surfaces: Window::get_default_surfaces(width, height),
-									did_fill: true
-								})
-							},
-							Err(error_info) => Err(RealmsError::GetSdlEventPumpError(error_info))
-						}
-						Err(error_info) => Err(RealmsError::CreateSdlCanvasError(error_info.to_string()))
-					},
-					Err(error_info) => Err(RealmsError::BuildSdlWindowError(error_info.to_string()))
-				}
+								window.set_display_mode(self.display_mode)?;
+							}
+							Ok(window)
+						},
+						Err(error_info) => Err(RealmsError::GetSdlEventPumpError(error_info))
+					}
+				},
 				Err(error_info) => Err(RealmsError::CreateSdlVideoSubsystemError(error_info))
 			}
 			Err(error_info) => Err(RealmsError::CreateSdlContextError(error_info))
 		}
 	}
 
+	fn build_sdl_window(&self, sdl_video_subsystem: &VideoSubsystem) -> Result<SdlWindow, RealmsError>
+	{
+		let mut sdl_window_builder = sdl_video_subsystem.window(self.title, self.width, self.height);
+		if let Some((x, y)) = self.position
+		{
+			sdl_window_builder.position(x, y);
+		}
+		if self.resizable
+		{
+			sdl_window_builder.resizable();
+		}
+		if self.borderless
+		{
+			sdl_window_builder.borderless();
+		}
+		if self.maximized
+		{
+			sdl_window_builder.maximized();
+		}
+		if self.high_dpi
+		{
+			sdl_window_builder.allow_highdpi();
+		}
+		if self.msaa_samples > 0
+		{
+			// Multisampling is an OpenGL context attribute, so it has to be set before the window is created.
+			let gl_attr = sdl_video_subsystem.gl_attr();
+			gl_attr.set_multisample_buffers(1);
+			gl_attr.set_multisample_samples(self.msaa_samples);
+			sdl_window_builder.opengl();
+		}
+
+		match sdl_window_builder.build()
+		{
+			Ok(sdl_window) => Ok(sdl_window),
+			Err(error_info) => Err(RealmsError::BuildSdlWindowError(error_info.to_string()))
+		}
+	}
+
+	// Returns the number of samples granted, or 0 if multisampling is off or the renderer is not OpenGL.
+	fn enable_msaa(&self, sdl_video_subsystem: &VideoSubsystem, sdl_canvas: &Canvas<SdlWindow>) -> u8
+	{
+		if self.msaa_samples == 0 || !sdl_canvas.info().name.starts_with("opengl")
+			{ return 0 }
+
+		const GL_MULTISAMPLE: u32 = 0x809D;
+		let gl_enable = sdl_video_subsystem.gl_get_proc_address("glEnable");
+		if gl_enable.is_null()
+			{ return 0 }
+		let gl_enable = unsafe { std::mem::transmute::<*const (), extern "system" fn(u32)>(gl_enable) };
+		gl_enable(GL_MULTISAMPLE);
+
+		match sdl_video_subsystem.gl_attr().multisample_buffers()
+		{
+			0 => 0,
+			_ => sdl_video_subsystem.gl_attr().multisample_samples(),
+		}
+	}
+
+	fn build_sdl_canvas(&self, sdl_window: SdlWindow) -> Result<Canvas<SdlWindow>, RealmsError>
+	{
+		let mut sdl_canvas_builder = sdl_window.into_canvas();
+		// Only this window's renderer is switched to OpenGL, rather than setting the process-wide render driver hint.
+		if self.msaa_samples > 0
+		{
+			if let Some(opengl_index) = sdl2::render::drivers().position(|driver| driver.name == "opengl")
+			{
+				sdl_canvas_builder = sdl_canvas_builder.index(opengl_index as u32);
+			}
+		}
+		if self.vsync
+		{
+			sdl_canvas_builder = sdl_canvas_builder.present_vsync();
+		}
+
+		match sdl_canvas_builder.build()
+		{
+			Ok(sdl_canvas) => Ok(sdl_canvas),
+			Err(error_info) => Err(RealmsError::CreateSdlCanvasError(error_info.to_string()))
+		}
+	}
+}
+
+impl<'a> Default for WindowBuilder<'a>
+{
+	fn default() -> WindowBuilder<'a>
+	{
+		WindowBuilder::new()
+	}
+}
+
+impl Window<'_>
+{
+	/// You must store the Window object as a **mutable** object, or SDL functions will fail.
+	/// 
+	/// Use `Window::builder()` for more options, such as making the window resizable.
+	pub fn new(title: &str, width: u32, height: u32) -> Result<Window, RealmsError>
+	{
+		WindowBuilder::new()
+			.title(title)
+			.size(width, height)
+			.build()
+	}
+
+	pub fn builder<'a>() -> WindowBuilder<'a>
+	{
+		WindowBuilder::new()
+	}
+
+	/// The current title, including any changes made with `set_title()`.
+	pub fn get_title(&self) -> String
+	{
+		self.sdl.canvas.window().title().to_string()
+	}
+
+	pub fn set_title(&mut self, title: &str) -> Result<(), RealmsError>
+	{
+		match self.sdl.canvas.window_mut().set_title(title)
+		{
+			Ok(()) => Ok(()),
+			Err(error_info) => Err(RealmsError::SetTitleError(error_info.to_string())),
+		}
+	}
+
+	/// The width of the drawing area. This is the same as the window width unless a `ResizePolicy` other than `Free` is set.
+	pub fn get_width(&self) -> u32
+	{
+		self.width
+	}
+
+	/// The height of the drawing area. This is the same as the window height unless a `ResizePolicy` other than `Free` is set.
+	pub fn get_height(&self) -> u32
+	{
+		self.height
+	}
+
+	/// The size of the window on the desktop, as `(width, height)`.
+	pub fn get_size(&self) -> (u32, u32)
+	{
+		self.sdl.canvas.window().size()
+	}
+
+	pub fn set_size(&mut self, width: u32, height: u32) -> Result<(), RealmsError>
+	{
+		match self.sdl.canvas.window_mut().set_size(width, height)
+		{
+			Ok(()) => Ok(()),
+			Err(error_info) => Err(RealmsError::SetWindowSizeError(error_info.to_string())),
+		}
+	}
+
+	/// The position of the window's top-left corner on the desktop, as `(x, y)`.
+	pub fn get_position(&self) -> (i32, i32)
+	{
+		self.sdl.canvas.window().position()
+	}
+
+	pub fn set_position(&mut self, x: i32, y: i32)
+	{
+		self.sdl.canvas.window_mut().set_position(WindowPos::Positioned(x), WindowPos::Positioned(y));
+	}
+
+	pub fn center(&mut self)
+	{
+		self.sdl.canvas.window_mut().set_position(WindowPos::Centered, WindowPos::Centered);
+	}
+
 	pub fn get_events(&mut self) -> Vec<Event>
 	{
-		let mut events: Vec<Event> = Vec::new();
+		self.poll_events().into_iter().map(|(event, _)| event).collect()
+	}
+
+	/// Like `get_events`, but each event also records when it happened and on which frame it was polled.
+	pub fn get_timed_events(&mut self) -> Vec<TimedEvent>
+	{
+		let frame = self.frame_count;
+		let events = self.poll_events();
+		let polled_at = Duration::from_millis(sdl_ticks() as u64);
+		events.into_iter().map(|(event, timestamp)| TimedEvent {
+			event: event,
+			timestamp: Duration::from_millis(timestamp as u64),
+			polled_at: polled_at,
+			frame: frame,
+		}).collect()
+	}
+
+	// Returns each event with its SDL timestamp, in milliseconds since SDL was initialised.
+	// Events that Realms detects itself are timestamped when they are detected.
+	fn poll_events(&mut self) -> Vec<(Event, u32)>
+	{
+		let mut events: Vec<(Event, u32)> = Vec::new();
+		let mut resized_to: Option<(i32, i32)> = None;
+		let mut added_controllers: Vec<u32> = Vec::new();
+		let mut removed_controllers: Vec<u32> = Vec::new();
 
 		for sdl_event in self.sdl.event_pump.poll_iter()
 		{
+			match sdl_event
+			{
+				SdlEvent::Window { win_event: SdlWindowEvent::SizeChanged(width, height), .. } => resized_to = Some((width, height)),
+				SdlEvent::ControllerDeviceAdded { which, .. } => added_controllers.push(which),
+				SdlEvent::ControllerDeviceRemoved { which, .. } => removed_controllers.push(which),
+				_ => {  }
+			}
+			let timestamp = sdl_event.get_timestamp();
 			let event = Event::from_sdl(sdl_event);
 			if matches!(event, Event::None)
-				{ break }
-			events.push(event);
+				{ continue }
+			events.push((event, timestamp));
+		}
+
+		// SDL only converts mouse positions itself when a logical size is set (letterboxing), so the
+		// other policies are converted here using the scale the events were sent under.
+		let (logical_width, _) = self.sdl.canvas.logical_size();
+		if logical_width == 0 && self.sdl.canvas.scale() != (1.0, 1.0)
+		{
+			for (event, _) in events.iter_mut()
+			{
+				self.mouse_to_logical(event);
+			}
+		}
+
+		let content_scale = self.query_content_scale();
+		if content_scale != self.content_scale
+		{
+			self.content_scale = content_scale;
+			events.push((Event::ContentScaleChanged(ContentScaleEvent {
+				x: content_scale.0,
+				y: content_scale.1,
+			}), sdl_ticks()));
+			let _ = self.update_scale();
+		}
+
+		if let Some((width, height)) = resized_to
+		{
+			self.handle_resize(width as u32, height as u32);
+		}
+
+		// Controllers only send events while they are open.
+		self.controllers.retain(|controller| !removed_controllers.contains(&controller.instance_id()));
+		for device_index in added_controllers
+		{
+			if let Ok(controller) = self.sdl.game_controller.open(device_index)
+			{
+				self.controllers.push(controller);
+			}
 		}
 
 		events
 	}
 
+	/// Converts a position in window coordinates to the game's drawing coordinates, accounting for
+	/// the resize policy (letterboxing or stretching) and Hi-DPI scaling.
+	///
+	/// Mouse events from `window.get_events()` are already converted under every resize policy, so this is only
+	/// needed for positions from other sources.
+	pub fn to_logical(&self, window_x: i32, window_y: i32) -> (i32, i32)
+	{
+		let mut logical_x: f32 = 0.0;
+		let mut logical_y: f32 = 0.0;
+		unsafe { sys::SDL_RenderWindowToLogical(self.sdl.canvas.raw(), window_x, window_y, &mut logical_x, &mut logical_y) };
+		(logical_x.floor() as i32, logical_y.floor() as i32)
+	}
+
+	fn mouse_to_logical(&self, event: &mut Event)
+	{
+		match event
+		{
+			Event::MouseMotion(motion) => (motion.x, motion.y) = self.to_logical(motion.x, motion.y),
+			Event::MouseDown(click) | Event::MouseUp(click) => (click.x, click.y) = self.to_logical(click.x, click.y),
+			_ => {  }
+		}
+	}
+
+	/// The current mouse position in drawing coordinates.
+	pub fn get_mouse_position(&self) -> (i32, i32)
+	{
+		let mouse_state = self.sdl.event_pump.mouse_state();
+		self.to_logical(mouse_state.x(), mouse_state.y())
+	}
+
+	pub fn get_capabilities(&self) -> &Capabilities
+	{
+		&self.capabilities
+	}
+
+	/// The number of multisample anti-aliasing samples per pixel the driver granted, which may be fewer than
+	/// requested with `WindowBuilder::msaa()`. `0` means multisampling is off.
+	pub fn get_msaa_samples(&self) -> u8
+	{
+		self.msaa_samples
+	}
+
+	/// The number of frames drawn with `window.draw()` so far.
+	pub fn get_frame_count(&self) -> u64
+	{
+		self.frame_count
+	}
+
+	/// Call once at the start of each frame to get the time since the previous frame.
+	pub fn new_frame(&mut self) -> GameTime
+	{
+		let now = Instant::now();
+		let delta = match self.last_frame
+		{
+			Some(last_frame) => now - last_frame,
+			None => Duration::ZERO,
+		};
+		self.last_frame = Some(now);
+		GameTime {
+			delta: delta,
+			elapsed: now - self.created_at,
+			frame: self.frame_count,
+		}
+	}
+
 	pub fn fill(&mut self, colour: Colour) -> Result<(), RealmsError>
 	{
+		blend::set_draw_blend_mode(&self.sdl.canvas, &BlendMode::None)?;
 		self.sdl.canvas.set_draw_color(colour.to_sdl());
 		self.did_fill = true;
 		match self.sdl.canvas.fill_rect(self.default_surfaces.fill_rect)
@@ -104,15 +569,508 @@ impl Window<'_>
 			Err(error_info) => Err(RealmsError::DrawRectError(error_info)),
 		}
 	}
-	
+
+	/// Clears the entire window, including any letterbox bars, to `colour`.
+	/// Can be used instead of `window.fill(colour)` at the beginning of each iteration.
+	pub fn clear(&mut self, colour: Colour)
+	{
+		self.sdl.canvas.set_draw_color(colour.to_sdl());
+		self.sdl.canvas.clear();
+		self.did_fill = true;
+	}
+
+	/// Allows `window.draw()` to be called without filling or clearing the screen first, so drawing builds up
+	/// over several frames (e.g. for trail effects).
+	///
+	/// SDL does not guarantee the previous frame is kept after it is presented, so the result depends on the
+	/// platform and renderer: on some it accumulates, on others it flickers between two buffers.
+	///
+	/// `set_brightness()` is only applied to frames that were filled or cleared, since applying it again over
+	/// the previous frame would fade the screen to black or white.
+	pub fn set_no_clear(&mut self, no_clear: bool)
+	{
+		self.no_clear = no_clear;
+	}
+
 	pub fn draw(&mut self)
 	{
-		if !self.did_fill
+		if !self.did_fill && !self.no_clear
 		{
 			panic!("Realms error: The screen was not filled before calling `window.draw()`. Make sure you call `window.fill(colour)` at the beginning of each iteration, or the screen will not display properly.");
 		}
+		// Brightness is drawn over the back buffer, so it would compound every frame the buffer is not refilled.
+		if self.brightness != 1.0 && self.did_fill
+		{
+			let _ = self.apply_brightness();
+		}
+		if let Some(frame_pacer) = &mut self.frame_pacer
+		{
+			frame_pacer.wait();
+		}
 		self.sdl.canvas.present();
 		self.did_fill = false;
+		self.frame_count += 1;
+	}
+
+	/// Returns the refresh rate (in Hz) of the monitor the window is currently on, or `0` if it is unknown.
+	pub fn get_refresh_rate(&self) -> Result<i32, RealmsError>
+	{
+		match self.sdl.canvas.window().display_mode()
+		{
+			Ok(display_mode) => Ok(display_mode.refresh_rate),
+			Err(error_info) => Err(RealmsError::GetDisplayModeError(error_info)),
+		}
+	}
+
+	/// When enabled, `window.draw()` waits so that frames are presented in time with the monitor's refresh rate.
+	/// If the refresh rate is unknown, 60 Hz is assumed.
+	pub fn set_frame_pacing(&mut self, enabled: bool) -> Result<(), RealmsError>
+	{
+		if !enabled
+		{
+			self.frame_pacer = None;
+			return Ok(());
+		}
+
+		let refresh_rate = match self.get_refresh_rate()?
+		{
+			rate if rate > 0 => rate as u32,
+			_ => 60,
+		};
+		self.frame_pacer = Some(FramePacer::new(refresh_rate));
+		Ok(())
+	}
+
+	pub fn set_always_on_top(&mut self, on_top: bool)
+	{
+		let sdl_on_top = if on_top { SDL_bool::SDL_TRUE } else { SDL_bool::SDL_FALSE };
+		unsafe { sys::SDL_SetWindowAlwaysOnTop(self.sdl.canvas.window().raw(), sdl_on_top) };
+	}
+
+	pub fn minimize(&mut self)
+	{
+		self.sdl.canvas.window_mut().minimize();
+	}
+
+	/// The same as `minimize()`. Sends `Event::Iconify` once the window is minimized.
+	pub fn iconify(&mut self)
+	{
+		self.minimize();
+	}
+
+	/// Whether the window is minimized, for example so the game can pause until it is restored.
+	pub fn is_iconified(&self) -> bool
+	{
+		self.sdl.canvas.window().window_flags() & SDL_WindowFlags::SDL_WINDOW_MINIMIZED as u32 != 0
+	}
+
+	pub fn is_maximized(&self) -> bool
+	{
+		self.sdl.canvas.window().window_flags() & SDL_WindowFlags::SDL_WINDOW_MAXIMIZED as u32 != 0
+	}
+
+	pub fn maximize(&mut self)
+	{
+		self.sdl.canvas.window_mut().maximize();
+	}
+
+	pub fn restore(&mut self)
+	{
+		self.sdl.canvas.window_mut().restore();
+	}
+
+	/// Raises the window above other windows and gives it input focus.
+	pub fn focus(&mut self)
+	{
+		self.sdl.canvas.window_mut().raise();
+	}
+
+	/// Flashes the window (or its taskbar icon) until the user focuses it.
+	pub fn request_attention(&mut self) -> Result<(), RealmsError>
+	{
+		match unsafe { sys::SDL_FlashWindow(self.sdl.canvas.window().raw(), SDL_FlashOperation::SDL_FLASH_UNTIL_FOCUSED) }
+		{
+			0 => Ok(()),
+			_ => Err(RealmsError::RequestAttentionError(sdl2::get_error())),
+		}
+	}
+
+	pub fn get_display_mode(&self) -> &DisplayMode
+	{
+		&self.display_mode
+	}
+
+	/// Switches between windowed, borderless and exclusive fullscreen.
+	/// Returning to `DisplayMode::Windowed` restores the window size from before it left windowed mode.
+	pub fn set_display_mode(&mut self, display_mode: DisplayMode) -> Result<(), RealmsError>
+	{
+		if self.display_mode == DisplayMode::Windowed
+		{
+			self.windowed_size = self.sdl.canvas.window().size();
+		}
+
+		let window = self.sdl.canvas.window_mut();
+		let result = match &display_mode
+		{
+			DisplayMode::Windowed => window.set_fullscreen(FullscreenType::Off)
+				.and_then(|()| window.set_size(self.windowed_size.0, self.windowed_size.1).map_err(|error_info| error_info.to_string())),
+			DisplayMode::Borderless => window.set_fullscreen(FullscreenType::Desktop),
+			DisplayMode::Exclusive(video_mode) => {
+				let sdl_display_mode = window.display_mode().map(|current| SdlDisplayMode::new(
+					current.format, video_mode.width as i32, video_mode.height as i32, video_mode.refresh_rate
+				));
+				sdl_display_mode
+					.and_then(|sdl_display_mode| window.set_display_mode(Some(sdl_display_mode)))
+					.and_then(|()| window.set_fullscreen(FullscreenType::True))
+			},
+		};
+
+		match result
+		{
+			Ok(()) => {
+				self.display_mode = display_mode;
+				Ok(())
+			},
+			Err(error_info) => Err(RealmsError::SetDisplayModeError(error_info)),
+		}
+	}
+
+	pub fn get_monitors(&self) -> Result<Vec<Monitor>, RealmsError>
+	{
+		let monitor_count = match self.sdl.video.num_video_displays()
+		{
+			Ok(monitor_count) => monitor_count,
+			Err(error_info) => return Err(RealmsError::GetMonitorError(error_info)),
+		};
+
+		let mut monitors: Vec<Monitor> = Vec::new();
+		for index in 0..monitor_count
+		{
+			let name = self.sdl.video.display_name(index).unwrap_or_default();
+			let bounds = match self.sdl.video.display_bounds(index)
+			{
+				Ok(bounds) => bounds,
+				Err(error_info) => return Err(RealmsError::GetMonitorError(error_info)),
+			};
+			let video_mode = match self.sdl.video.current_display_mode(index)
+			{
+				Ok(sdl_display_mode) => VideoMode::from_sdl(&sdl_display_mode),
+				Err(error_info) => return Err(RealmsError::GetDisplayModeError(error_info)),
+			};
+			monitors.push(Monitor {
+				index: index,
+				name: name,
+				x: bounds.x(),
+				y: bounds.y(),
+				width: bounds.width(),
+				height: bounds.height(),
+				video_mode: video_mode,
+			});
+		}
+		Ok(monitors)
+	}
+
+	/// Returns the index of the monitor the window is currently on.
+	pub fn get_current_monitor(&self) -> Result<i32, RealmsError>
+	{
+		match self.sdl.canvas.window().display_index()
+		{
+			Ok(index) => Ok(index),
+			Err(error_info) => Err(RealmsError::GetMonitorError(error_info)),
+		}
+	}
+
+	/// Moves the window to `monitor` and makes it exclusive fullscreen at that monitor's current video mode.
+	pub fn set_fullscreen(&mut self, monitor: &Monitor) -> Result<(), RealmsError>
+	{
+		if self.display_mode != DisplayMode::Windowed
+		{
+			self.set_display_mode(DisplayMode::Windowed)?;
+		}
+		self.sdl.canvas.window_mut().set_position(WindowPos::Positioned(monitor.x), WindowPos::Positioned(monitor.y));
+		self.set_display_mode(DisplayMode::Exclusive(monitor.video_mode.clone()))
+	}
+
+	/// Moves the window to `monitor` and covers it with a borderless window at the desktop resolution,
+	/// which is faster to alt-tab out of than exclusive fullscreen.
+	pub fn set_borderless(&mut self, monitor: &Monitor) -> Result<(), RealmsError>
+	{
+		if self.display_mode != DisplayMode::Windowed
+		{
+			self.set_display_mode(DisplayMode::Windowed)?;
+		}
+		self.sdl.canvas.window_mut().set_position(WindowPos::Positioned(monitor.x), WindowPos::Positioned(monitor.y));
+		self.set_display_mode(DisplayMode::Borderless)
+	}
+
+	/// Switches between `DisplayMode::Windowed` and `DisplayMode::Borderless` on the current monitor.
+	pub fn toggle_borderless(&mut self) -> Result<(), RealmsError>
+	{
+		match self.display_mode
+		{
+			DisplayMode::Borderless => self.set_display_mode(DisplayMode::Windowed),
+			_ => self.set_display_mode(DisplayMode::Borderless),
+		}
+	}
+
+	/// Leaves fullscreen and resizes the window to `width` x `height`.
+	pub fn set_windowed(&mut self, width: u32, height: u32) -> Result<(), RealmsError>
+	{
+		self.windowed_size = (width, height);
+		if self.display_mode != DisplayMode::Windowed
+		{
+			return self.set_display_mode(DisplayMode::Windowed);
+		}
+		match self.sdl.canvas.window_mut().set_size(width, height)
+		{
+			Ok(()) => Ok(()),
+			Err(error_info) => Err(RealmsError::SetWindowSizeError(error_info.to_string())),
+		}
+	}
+
+	/// Lists the video modes supported by the monitor at `monitor_index`, for use with `DisplayMode::Exclusive`.
+	pub fn get_video_modes(&self, monitor_index: i32) -> Result<Vec<VideoMode>, RealmsError>
+	{
+		let mode_count = match self.sdl.video.num_display_modes(monitor_index)
+		{
+			Ok(mode_count) => mode_count,
+			Err(error_info) => return Err(RealmsError::GetDisplayModeError(error_info)),
+		};
+
+		let mut video_modes: Vec<VideoMode> = Vec::new();
+		for mode_index in 0..mode_count
+		{
+			match self.sdl.video.display_mode(monitor_index, mode_index)
+			{
+				Ok(sdl_display_mode) => video_modes.push(VideoMode::from_sdl(&sdl_display_mode)),
+				Err(error_info) => return Err(RealmsError::GetDisplayModeError(error_info)),
+			}
+		}
+		Ok(video_modes)
+	}
+
+	/// Sets the gamma of the monitor the window is on, where `1.0` is unchanged.
+	/// Not every platform supports this (e.g. Wayland), so `set_brightness` is usually the safer choice.
+	pub fn set_gamma(&mut self, gamma: f32) -> Result<(), RealmsError>
+	{
+		match self.sdl.canvas.window_mut().set_brightness(gamma as f64)
+		{
+			Ok(()) => Ok(()),
+			Err(error_info) => Err(RealmsError::SetGammaError(error_info)),
+		}
+	}
+
+	/// Brightens or darkens everything drawn to the window, where `1.0` is unchanged, `0.0` is black
+	/// and `2.0` adds full white. Applied by `window.draw()`, so it works on every platform.
+	/// With `set_no_clear(true)`, it is skipped on frames that were not filled or cleared.
+	pub fn set_brightness(&mut self, brightness: f32)
+	{
+		self.brightness = brightness.clamp(0.0, 2.0);
+	}
+
+	pub fn get_brightness(&self) -> f32
+	{
+		self.brightness
+	}
+
+	fn apply_brightness(&mut self) -> Result<(), RealmsError>
+	{
+		let (blend_mode, level) = match self.brightness < 1.0
+		{
+			true => (BlendMode::Modulate, self.brightness),
+			false => (BlendMode::Additive, self.brightness - 1.0),
+		};
+		let level = (level * 255.0) as u8;
+		blend::set_draw_blend_mode(&self.sdl.canvas, &blend_mode)?;
+		self.sdl.canvas.set_draw_color(Colour::from_rgb(level, level, level).to_sdl());
+		match self.sdl.canvas.fill_rect(None)
+		{
+			Ok(()) => Ok(()),
+			Err(error_info) => Err(RealmsError::DrawRectError(error_info)),
+		}
+	}
+
+	/// Sets how opaque the whole window is, from `0.0` (invisible) to `1.0` (opaque).
+	pub fn set_opacity(&mut self, opacity: f32) -> Result<(), RealmsError>
+	{
+		match self.sdl.canvas.window_mut().set_opacity(opacity.clamp(0.0, 1.0))
+		{
+			Ok(()) => Ok(()),
+			Err(error_info) => Err(RealmsError::SetOpacityError(error_info)),
+		}
+	}
+
+	/// Turns vsync on or off after the window has been created. Requires SDL 2.0.18 or later.
+	pub fn set_vsync(&mut self, vsync: bool) -> Result<(), RealmsError>
+	{
+		match unsafe { sys::SDL_RenderSetVSync(self.sdl.canvas.raw(), vsync as i32) }
+		{
+			0 => {
+				self.capabilities.vsync = vsync;
+				Ok(())
+			},
+			_ => Err(RealmsError::SetVsyncError(sdl2::get_error())),
+		}
+	}
+
+	/// Loads controller mappings from a file in the SDL_GameControllerDB format
+	/// (<https://github.com/gabomdq/SDL_GameControllerDB>), so that third-party controllers report
+	/// the standard A/B/X/Y and stick layout. Returns the number of mappings added.
+	/// Controllers that were already connected pick up the new mappings when they are reconnected.
+	pub fn load_controller_mappings(&mut self, filepath: &str) -> Result<i32, RealmsError>
+	{
+		match self.sdl.game_controller.load_mappings(filepath)
+		{
+			Ok(mapping_count) => Ok(mapping_count),
+			Err(error_info) => Err(RealmsError::AddControllerMappingError(error_info.to_string())),
+		}
+	}
+
+	/// Adds a single mapping line in the SDL_GameControllerDB format.
+	pub fn add_controller_mapping(&mut self, mapping: &str) -> Result<(), RealmsError>
+	{
+		match self.sdl.game_controller.add_mapping(mapping)
+		{
+			Ok(_) => Ok(()),
+			Err(error_info) => Err(RealmsError::AddControllerMappingError(error_info.to_string())),
+		}
+	}
+
+	pub fn set_resizable(&mut self, resizable: bool)
+	{
+		let sdl_resizable = if resizable { SDL_bool::SDL_TRUE } else { SDL_bool::SDL_FALSE };
+		unsafe { sys::SDL_SetWindowResizable(self.sdl.canvas.window().raw(), sdl_resizable) };
+	}
+
+	pub fn set_min_size(&mut self, width: u32, height: u32) -> Result<(), RealmsError>
+	{
+		match self.sdl.canvas.window_mut().set_minimum_size(width, height)
+		{
+			Ok(()) => Ok(()),
+			Err(error_info) => Err(RealmsError::SetWindowSizeError(error_info.to_string())),
+		}
+	}
+
+	pub fn set_max_size(&mut self, width: u32, height: u32) -> Result<(), RealmsError>
+	{
+		match self.sdl.canvas.window_mut().set_maximum_size(width, height)
+		{
+			Ok(()) => Ok(()),
+			Err(error_info) => Err(RealmsError::SetWindowSizeError(error_info.to_string())),
+		}
+	}
+
+	/// Sets the smallest and largest sizes the user can resize the window to, as `(width, height)`.
+	pub fn set_size_limits(&mut self, min: (u32, u32), max: (u32, u32)) -> Result<(), RealmsError>
+	{
+		self.set_min_size(min.0, min.1)?;
+		self.set_max_size(max.0, max.1)
+	}
+
+	/// Keeps the window at a `numerator:denominator` aspect ratio (e.g. `16, 9`) when the user resizes it.
+	/// The height is adjusted to match the new width.
+	pub fn set_aspect_ratio(&mut self, numerator: u32, denominator: u32)
+	{
+		self.aspect_ratio = match numerator == 0 || denominator == 0
+		{
+			true => None,
+			false => Some((numerator, denominator)),
+		};
+	}
+
+	pub fn clear_aspect_ratio(&mut self)
+	{
+		self.aspect_ratio = None;
+	}
+
+	pub fn set_resize_policy(&mut self, resize_policy: ResizePolicy) -> Result<(), RealmsError>
+	{
+		let (window_width, window_height) = self.sdl.canvas.window().size();
+		self.resize_policy = resize_policy;
+		let result = match self.resize_policy
+		{
+			ResizePolicy::Free => {
+				self.width = window_width;
+				self.height = window_height;
+				self.default_surfaces = Window::get_default_surfaces(window_width, window_height);
+				self.reset_logical_size().and_then(|()| self.update_scale())
+			},
+			ResizePolicy::Stretch => {
+				self.reset_logical_size().and_then(|()| self.update_scale())
+			},
+			ResizePolicy::Letterbox => {
+				self.sdl.canvas.set_logical_size(self.width, self.height).map_err(|error_info| error_info.to_string())
+			},
+		};
+		match result
+		{
+			Ok(()) => Ok(()),
+			Err(error_info) => Err(RealmsError::SetResizePolicyError(error_info)),
+		}
+	}
+
+	/// How many framebuffer pixels there are per window unit, as `(x, y)`. This is `(1.0, 1.0)` unless the window
+	/// was built with `high_dpi(true)` and is on a Hi-DPI display (e.g. `(2.0, 2.0)` on a retina display).
+	/// Realms scales drawing by this automatically, so coordinates stay in window units.
+	pub fn content_scale(&self) -> (f32, f32)
+	{
+		self.content_scale
+	}
+
+	fn query_content_scale(&self) -> (f32, f32)
+	{
+		let (window_width, window_height) = self.sdl.canvas.window().size();
+		let (output_width, output_height) = self.sdl.canvas.output_size().unwrap_or((window_width, window_height));
+		if window_width == 0 || window_height == 0
+			{ return (1.0, 1.0) }
+		(output_width as f32 / window_width as f32, output_height as f32 / window_height as f32)
+	}
+
+	fn reset_logical_size(&mut self) -> Result<(), String>
+	{
+		self.sdl.canvas.set_logical_size(0, 0).map_err(|error_info| error_info.to_string())?;
+		self.sdl.canvas.set_scale(1.0, 1.0)
+	}
+
+	// Letterboxing is scaled by SDL through the logical size, so only the other policies need an explicit scale.
+	fn update_scale(&mut self) -> Result<(), String>
+	{
+		let (scale_x, scale_y) = self.content_scale;
+		match self.resize_policy
+		{
+			ResizePolicy::Free => self.sdl.canvas.set_scale(scale_x, scale_y),
+			ResizePolicy::Stretch => {
+				let (window_width, window_height) = self.sdl.canvas.window().size();
+				self.sdl.canvas.set_scale(
+					window_width as f32 / self.width as f32 * scale_x,
+					window_height as f32 / self.height as f32 * scale_y
+				)
+			},
+			ResizePolicy::Letterbox => Ok(()),
+		}
+	}
+
+	fn handle_resize(&mut self, new_width: u32, new_height: u32)
+	{
+		if let Some((numerator, denominator)) = self.aspect_ratio
+		{
+			let locked_height = new_width * denominator / numerator;
+			if locked_height != new_height
+			{
+				// The locked size is only a request: the window manager may refuse or clamp it (for example when the
+				// window is maximized or tiled), so the policy is applied to the size SDL reported. If the request is
+				// granted, another `SizeChanged` event follows with the locked size.
+				let _ = self.sdl.canvas.window_mut().set_size(new_width, locked_height);
+			}
+		}
+
+		if let ResizePolicy::Free = self.resize_policy
+		{
+			self.width = new_width;
+			self.height = new_height;
+			self.default_surfaces = Window::get_default_surfaces(new_width, new_height);
+		}
+		let _ = self.update_scale();
 	}
 
 	fn get_default_surfaces(width: u32, height: u32) -> DefaultSurfaces
@@ -122,3 +1080,17 @@ impl Window<'_>
 		}
 	}
 }
+
+impl Drop for Window<'_>
+{
+	fn drop(&mut self)
+	{
+		render::warn_leaked_resources();
+	}
+}
+
+// Milliseconds since SDL was initialised, on the same clock as SDL event timestamps.
+fn sdl_ticks() -> u32
+{
+	unsafe { sys::SDL_GetTicks() }
+}